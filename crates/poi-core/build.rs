@@ -0,0 +1,46 @@
+//! Generates the dictionary Bloom filter from `data/english-words.txt`.
+//!
+//! Output is a pure function of the word file and `src/bloom.rs`, so every
+//! build (and every validator's copy of the program) carries the same bits.
+
+use std::{env, fs, path::Path};
+
+#[path = "src/bloom.rs"]
+mod bloom;
+
+const SOURCE: &str = "data/english-words.txt";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SOURCE}");
+    println!("cargo:rerun-if-changed=src/bloom.rs");
+
+    let text = fs::read_to_string(SOURCE).expect("read word source");
+    let mut bits = [0u64; bloom::BLOOM_WORDS];
+    let mut count = 0usize;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        assert!(
+            line.bytes().all(|b| b.is_ascii_lowercase()),
+            "{SOURCE}: {line:?} is not lowercase ASCII"
+        );
+        for idx in bloom::bloom_indices(line.as_bytes()) {
+            bits[idx / 64] |= 1 << (idx % 64);
+        }
+        count += 1;
+    }
+
+    let mut out = String::new();
+    out.push_str("// Generated by build.rs from data/english-words.txt. Do not edit.\n");
+    out.push_str(&format!("pub const ENGLISH_WORD_COUNT: usize = {count};\n"));
+    out.push_str(&format!("pub static ENGLISH_BLOOM: [u64; {}] = [\n", bloom::BLOOM_WORDS));
+    for chunk in bits.chunks(4) {
+        let row: Vec<String> = chunk.iter().map(|w| format!("0x{w:016x}")).collect();
+        out.push_str(&format!("    {},\n", row.join(", ")));
+    }
+    out.push_str("];\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("dictionary.rs");
+    fs::write(dest, out).expect("write dictionary.rs");
+}
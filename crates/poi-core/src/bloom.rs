@@ -0,0 +1,30 @@
+//! Bloom filter layout and hashing, shared by `build.rs` (which builds the
+//! filter) and `dictionary` (which queries it). Keep this file free of crate
+//! dependencies: the build script includes it with `#[path]`.
+
+/// Filter size in bits (8 KiB). ~4 000 words at 5 hashes gives ~0.2% false positives.
+pub const BLOOM_BITS: usize = 1 << 16;
+/// Filter size in u64 words.
+pub const BLOOM_WORDS: usize = BLOOM_BITS / 64;
+/// Hash functions per word.
+pub const BLOOM_HASHES: usize = 5;
+
+/// Bit positions for `word`: double hashing over two FNV-1a 64 streams.
+pub fn bloom_indices(word: &[u8]) -> [usize; BLOOM_HASHES] {
+    let mut h1: u64 = 0xcbf29ce484222325;
+    let mut h2: u64 = 0x84222325cbf29ce4;
+    let mut i = 0;
+    while i < word.len() {
+        h1 = (h1 ^ word[i] as u64).wrapping_mul(0x100000001b3);
+        h2 = (h2 ^ word[i] as u64).wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    h2 |= 1; // odd step, so the probes are distinct
+    let mut out = [0usize; BLOOM_HASHES];
+    let mut k = 0;
+    while k < BLOOM_HASHES {
+        out[k] = (h1.wrapping_add((k as u64).wrapping_mul(h2)) % BLOOM_BITS as u64) as usize;
+        k += 1;
+    }
+    out
+}
@@ -0,0 +1,511 @@
+//! Challenge kinds for Proof of Inference.
+//!
+//! Each epoch the challenge seed picks one kind among those enabled in
+//! `TextRules::challenge_kinds`; a mask of 0 means the original "include these
+//! words in order" challenge only. Every kind has a verifier, run after
+//! `verify_text`'s rules, and a description clients show to the text generator.
+//!
+//! Seed bytes: 0–15 required words, 16–20 the other kinds' parameters,
+//! 30 the kind, 31 the language (see `lang::select`).
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::lang::{self, LanguagePack};
+use crate::verify::{letter_at, letter_before, TextRules};
+use crate::words::{self, RequiredWords, WordSource, MAX_WORD_LEN};
+
+/// `TextRules::challenge_kinds` bits.
+pub const KIND_REQUIRED_WORDS: u8 = 1 << 0;
+pub const KIND_ACROSTIC: u8 = 1 << 1;
+pub const KIND_SENTENCE_START: u8 = 1 << 2;
+pub const KIND_QUESTION_WORD: u8 = 1 << 3;
+pub const KIND_SENTENCE_PARITY: u8 = 1 << 4;
+pub const KIND_CATEGORY: u8 = 1 << 5;
+pub const KIND_PATTERN_WORDS: u8 = 1 << 6;
+pub const KIND_ALL: u8 = KIND_REQUIRED_WORDS
+    | KIND_ACROSTIC
+    | KIND_SENTENCE_START
+    | KIND_QUESTION_WORD
+    | KIND_SENTENCE_PARITY
+    | KIND_CATEGORY
+    | KIND_PATTERN_WORDS;
+
+/// Acrostic words are 4–5 letters: as many sentences, at 5+ words each, fit in a text.
+const ACROSTIC_MAX_LEN: usize = 5;
+/// Distinct category words needed at tier 0; tiers add one every two.
+const CATEGORY_BASE_COUNT: u8 = 3;
+
+/// Word categories for `Challenge::Category` (English packs only).
+pub struct Category {
+    pub name: &'static str,
+    pub words: &'static [&'static str], // lowercase ASCII, ≤ 32
+}
+
+pub const CATEGORIES: [Category; 6] = [
+    Category {
+        name: "colours",
+        words: &["red", "orange", "yellow", "green", "blue", "purple", "pink", "brown", "black", "white", "grey", "gray", "gold", "silver"],
+    },
+    Category {
+        name: "animals",
+        words: &["dog", "cat", "horse", "cow", "sheep", "goat", "pig", "bird", "fish", "fox", "wolf", "bear", "deer", "rabbit", "mouse", "owl", "duck", "lion", "tiger", "eagle"],
+    },
+    Category {
+        name: "weather",
+        words: &["rain", "snow", "wind", "storm", "cloud", "fog", "sun", "frost", "thunder", "hail", "mist", "ice", "sunshine", "breeze"],
+    },
+    Category {
+        name: "family members",
+        words: &["mother", "father", "sister", "brother", "son", "daughter", "aunt", "uncle", "cousin", "grandmother", "grandfather", "parent", "child", "wife", "husband"],
+    },
+    Category {
+        name: "foods",
+        words: &["bread", "cheese", "apple", "rice", "soup", "meat", "egg", "milk", "butter", "honey", "sugar", "salt", "cake", "fruit", "potato"],
+    },
+    Category {
+        name: "parts of the body",
+        words: &["hand", "head", "eye", "ear", "nose", "mouth", "arm", "leg", "foot", "finger", "face", "hair", "shoulder", "knee", "neck"],
+    },
+];
+
+/// One epoch's challenge (fixed-size, no heap).
+pub enum Challenge {
+    /// Words must appear in order as whole words (checked by `verify_text`)
+    RequiredWords(RequiredWords),
+    /// The first letters of the first `len` sentences spell `word`
+    Acrostic { word: [u8; MAX_WORD_LEN], len: usize },
+    /// Some sentence starts with `word`
+    SentenceStart { word: [u8; MAX_WORD_LEN], len: usize },
+    /// `word` appears in a sentence ending with `?`
+    QuestionWord { word: [u8; MAX_WORD_LEN], len: usize },
+    /// The number of sentences ending in . ! ? is even (or odd)
+    SentenceParity { even: bool },
+    /// At least `count` different words from `CATEGORIES[index]`
+    Category { index: u8, count: u8 },
+    /// Required words drawn by the part-of-speech pattern `words::PATTERNS[pattern]`
+    PatternWords { pattern: u8, words: RequiredWords },
+}
+
+/// Why a text failed its challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeRejection {
+    Acrostic,
+    SentenceStart,
+    QuestionWord,
+    SentenceParity,
+    Category,
+}
+
+/// Pick this epoch's challenge kind from `rules.challenge_kinds` (0 = required
+/// words only) and derive its parameters from `seed`; words come from `list`
+/// (the pack's, see `LanguagePack::wordlist_for`, or an on-chain `WordList`)
+/// and follow `rules.word_derivation` and `extra_words`. Kinds the pack can't
+/// serve fall back to required words.
+pub fn derive(
+    rules: &TextRules,
+    pack: &LanguagePack,
+    list: &dyn WordSource,
+    seed: &[u8; 32],
+    difficulty: u64,
+) -> Challenge {
+    let kinds = rules.challenge_kinds;
+    let enabled = if kinds & KIND_ALL == 0 { KIND_REQUIRED_WORDS } else { kinds & KIND_ALL };
+    let mut pick = seed[30] % enabled.count_ones() as u8;
+    let mut kind = 0;
+    for bit in 0..8 {
+        if enabled & (1 << bit) != 0 {
+            if pick == 0 {
+                kind = 1 << bit;
+                break;
+            }
+            pick -= 1;
+        }
+    }
+
+    let version = rules.word_derivation;
+    let count = words::word_count(difficulty, rules.extra_words, version);
+    let raw = ((seed[16] as usize) << 8) | seed[17] as usize;
+    let challenge = match kind {
+        KIND_ACROSTIC => pick_word(list, raw, |w| w.len() <= ACROSTIC_MAX_LEN && w.is_ascii())
+            .map(|(word, len)| Challenge::Acrostic { word, len }),
+        KIND_SENTENCE_START => pick_word(list, raw, |w| w[0].is_ascii())
+            .map(|(word, len)| Challenge::SentenceStart { word, len }),
+        KIND_QUESTION_WORD => pick_word(list, raw, |_| true)
+            .map(|(word, len)| Challenge::QuestionWord { word, len }),
+        KIND_SENTENCE_PARITY => Some(Challenge::SentenceParity { even: seed[18] & 1 == 0 }),
+        KIND_CATEGORY if pack.code == lang::EN.code => Some(Challenge::Category {
+            index: seed[19] % CATEGORIES.len() as u8,
+            count: CATEGORY_BASE_COUNT + words::difficulty_tier(difficulty) / 2,
+        }),
+        KIND_PATTERN_WORDS => {
+            let (pattern, words) = words::derive_pattern_words_from(list, seed, count, version);
+            Some(Challenge::PatternWords { pattern: pattern as u8, words })
+        }
+        _ => None,
+    };
+    challenge.unwrap_or_else(|| Challenge::RequiredWords(words::derive_words_versioned(list, seed, count, version)))
+}
+
+/// First word at or after `raw % list.len()` (wrapping) that satisfies `ok`.
+fn pick_word(list: &dyn WordSource, raw: usize, ok: impl Fn(&[u8]) -> bool) -> Option<([u8; MAX_WORD_LEN], usize)> {
+    if list.is_empty() {
+        return None;
+    }
+    let start = raw % list.len();
+    for k in 0..list.len() {
+        let w = list.word((start + k) % list.len());
+        if ok(w) && w.len() <= MAX_WORD_LEN {
+            let mut word = [0u8; MAX_WORD_LEN];
+            word[..w.len()].copy_from_slice(w);
+            return Some((word, w.len()));
+        }
+    }
+    None
+}
+
+impl Challenge {
+    /// `KIND_*` bit of this challenge.
+    pub fn kind(&self) -> u8 {
+        match self {
+            Challenge::RequiredWords(_) => KIND_REQUIRED_WORDS,
+            Challenge::Acrostic { .. } => KIND_ACROSTIC,
+            Challenge::SentenceStart { .. } => KIND_SENTENCE_START,
+            Challenge::QuestionWord { .. } => KIND_QUESTION_WORD,
+            Challenge::SentenceParity { .. } => KIND_SENTENCE_PARITY,
+            Challenge::Category { .. } => KIND_CATEGORY,
+            Challenge::PatternWords { .. } => KIND_PATTERN_WORDS,
+        }
+    }
+
+    /// Words `verify_text` must find in order; empty for other kinds.
+    pub fn required_words(&self) -> RequiredWords {
+        match self {
+            Challenge::RequiredWords(rw) | Challenge::PatternWords { words: rw, .. } => *rw,
+            _ => RequiredWords::EMPTY,
+        }
+    }
+
+    /// Check `text` (already past `verify_text`) against the challenge.
+    pub fn verify(&self, pack: &LanguagePack, text: &[u8]) -> Result<(), ChallengeRejection> {
+        match *self {
+            Challenge::RequiredWords(_) | Challenge::PatternWords { .. } => Ok(()),
+            Challenge::Acrostic { ref word, len } => {
+                let mut n = 0;
+                for_each_sentence(pack, text, |sentence, _| {
+                    if n < len {
+                        let initial = first_letter(pack, sentence).map(|p| sentence[p].to_ascii_lowercase());
+                        if initial != Some(word[n]) {
+                            return false;
+                        }
+                        n += 1;
+                    }
+                    n < len
+                });
+                if n == len { Ok(()) } else { Err(ChallengeRejection::Acrostic) }
+            }
+            Challenge::SentenceStart { ref word, len } => {
+                let mut found = false;
+                for_each_sentence(pack, text, |sentence, _| {
+                    found = first_letter(pack, sentence).is_some_and(|p| word_at(pack, sentence, p, &word[..len]));
+                    !found
+                });
+                if found { Ok(()) } else { Err(ChallengeRejection::SentenceStart) }
+            }
+            Challenge::QuestionWord { ref word, len } => {
+                let mut found = false;
+                for_each_sentence(pack, text, |sentence, end| {
+                    if end == b'?' {
+                        found = (0..sentence.len()).any(|p| {
+                            !letter_before(pack, sentence, p) && word_at(pack, sentence, p, &word[..len])
+                        });
+                    }
+                    !found
+                });
+                if found { Ok(()) } else { Err(ChallengeRejection::QuestionWord) }
+            }
+            Challenge::SentenceParity { even } => {
+                let mut count = 0u32;
+                for_each_sentence(pack, text, |_, end| {
+                    if end != 0 {
+                        count += 1;
+                    }
+                    true
+                });
+                if (count & 1 == 0) == even { Ok(()) } else { Err(ChallengeRejection::SentenceParity) }
+            }
+            Challenge::Category { index, count } => {
+                let category = &CATEGORIES[index as usize];
+                let mut seen: u32 = 0; // bit per category word
+                let mut p = 0;
+                while p < text.len() {
+                    if letter_at(pack, text, p) && !letter_before(pack, text, p) {
+                        if let Some(k) = category.words.iter().position(|w| word_at(pack, text, p, w.as_bytes())) {
+                            seen |= 1 << k;
+                        }
+                    }
+                    p += 1;
+                }
+                if seen.count_ones() >= count as u32 { Ok(()) } else { Err(ChallengeRejection::Category) }
+            }
+        }
+    }
+
+    /// What the text must do, for miners and text generators.
+    pub fn describe(&self) -> String {
+        let quoted = |word: &[u8]| String::from_utf8_lossy(word).into_owned();
+        match *self {
+            Challenge::RequiredWords(ref rw) => {
+                let list: Vec<String> = (0..rw.count).map(|i| quoted(&rw.words[i][..rw.lens[i]])).collect();
+                format!("Use these words in this order, as whole words: {}", list.join(", "))
+            }
+            Challenge::PatternWords { words: ref rw, .. } => {
+                let list: Vec<String> = (0..rw.count)
+                    .map(|i| format!("{} ({})", quoted(&rw.words[i][..rw.lens[i]]), rw.parts[i].name()))
+                    .collect();
+                format!("Use these words in this order, as whole words: {}", list.join(", "))
+            }
+            Challenge::Acrostic { ref word, len } => format!(
+                "The first letters of the first {} sentences spell \"{}\"",
+                len,
+                quoted(&word[..len])
+            ),
+            Challenge::SentenceStart { ref word, len } => {
+                format!("Start a sentence with the word \"{}\"", quoted(&word[..len]))
+            }
+            Challenge::QuestionWord { ref word, len } => {
+                format!("Use the word \"{}\" in a question", quoted(&word[..len]))
+            }
+            Challenge::SentenceParity { even } => format!(
+                "Write an {} number of sentences",
+                if even { "even" } else { "odd" }
+            ),
+            Challenge::Category { index, count } => {
+                let category = &CATEGORIES[index as usize];
+                format!(
+                    "Use at least {} different words for {}, from: {}",
+                    count,
+                    category.name,
+                    category.words.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// Call `f(sentence, terminator)` for each sentence until it returns false.
+/// Sentences run up to the next `.`, `!` or `?` (the terminator, 0 for an
+/// unterminated tail) and must contain a letter, so "..." adds none.
+fn for_each_sentence(pack: &LanguagePack, text: &[u8], mut f: impl FnMut(&[u8], u8) -> bool) {
+    let mut start = 0;
+    let mut p = 0;
+    while p <= text.len() {
+        let end = if p < text.len() { text[p] } else { 0 };
+        if matches!(end, b'.' | b'!' | b'?' | 0) {
+            let sentence = &text[start..p];
+            if first_letter(pack, sentence).is_some() && !f(sentence, end) {
+                return;
+            }
+            start = p + 1;
+        }
+        p += 1;
+    }
+}
+
+/// Position of the first letter (ASCII or the pack's accented letters).
+fn first_letter(pack: &LanguagePack, sentence: &[u8]) -> Option<usize> {
+    (0..sentence.len()).find(|&p| letter_at(pack, sentence, p))
+}
+
+/// `word` (lowercase) starts at `pos` and ends at a word boundary, ignoring case.
+fn word_at(pack: &LanguagePack, text: &[u8], pos: usize, word: &[u8]) -> bool {
+    let mut i = pos;
+    let mut j = 0;
+    while j < word.len() {
+        if i >= text.len() {
+            return false;
+        }
+        if text[i] < 0x80 {
+            if text[i].to_ascii_lowercase() != word[j] {
+                return false;
+            }
+            i += 1;
+            j += 1;
+        } else {
+            if i + 1 >= text.len() || j + 1 >= word.len() {
+                return false;
+            }
+            let cp = lang::decode2(text[i], text[i + 1]).map(lang::lower_cp);
+            if cp.is_none() || cp != lang::decode2(word[j], word[j + 1]) {
+                return false;
+            }
+            i += 2;
+            j += 2;
+        }
+    }
+    !letter_at(pack, text, i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::PACKS;
+
+    const TEXT: &str = "Sometimes the river rises after a long night of rain. \
+        Tonight the wind is calm and the fox sleeps under the old bridge. \
+        Others say the storm will return before morning, with snow on the hills. \
+        Really, who can tell what the weather will bring to the valley tomorrow? \
+        Even my mother only laughs and puts more bread on the table.";
+
+    fn kinds(kinds: u8) -> TextRules {
+        TextRules { challenge_kinds: kinds, ..TextRules::DEFAULT }
+    }
+
+    fn word(w: &str) -> ([u8; MAX_WORD_LEN], usize) {
+        let mut word = [0u8; MAX_WORD_LEN];
+        word[..w.len()].copy_from_slice(w.as_bytes());
+        (word, w.len())
+    }
+
+    #[test]
+    fn test_derive() {
+        let mut seed = [7u8; 32];
+        // Mask 0 keeps the original challenge
+        let c = derive(&kinds(0), &lang::EN, lang::EN.wordlist, &seed, 8);
+        assert_eq!(c.kind(), KIND_REQUIRED_WORDS);
+        assert_eq!(c.required_words().count, 3);
+
+        // Byte 30 picks among the enabled kinds
+        for (pick, kind) in [(0, KIND_ACROSTIC), (1, KIND_SENTENCE_PARITY), (2, KIND_CATEGORY)] {
+            seed[30] = pick;
+            let c = derive(&kinds(KIND_ACROSTIC | KIND_SENTENCE_PARITY | KIND_CATEGORY), &lang::EN, lang::EN.wordlist, &seed, 8);
+            assert_eq!(c.kind(), kind);
+            assert_eq!(c.required_words().count, 0);
+        }
+
+        // Acrostic words fit: ASCII, 4-5 letters
+        for hi in 0..=255u8 {
+            seed[16] = hi;
+            if let Challenge::Acrostic { word, len } = derive(&kinds(KIND_ACROSTIC), &lang::ES, lang::ES.wordlist, &seed, 8) {
+                assert!((4..=ACROSTIC_MAX_LEN).contains(&len) && word[..len].is_ascii());
+            } else {
+                panic!("no acrostic for seed byte {hi}");
+            }
+        }
+
+        // Categories are English-only; other packs fall back to required words
+        assert_eq!(derive(&kinds(KIND_CATEGORY), &lang::EN, lang::EN.wordlist, &seed, 8).kind(), KIND_CATEGORY);
+        assert_eq!(derive(&kinds(KIND_CATEGORY), &lang::DE, lang::DE.wordlist, &seed, 8).kind(), KIND_REQUIRED_WORDS);
+        assert!(matches!(derive(&kinds(KIND_CATEGORY), &lang::EN, lang::EN.wordlist, &seed, 50), Challenge::Category { count: 5, .. }));
+    }
+
+    #[test]
+    fn test_pattern_words() {
+        // Group sizes match the comment-marked groups
+        assert_eq!(words::ENGLISH.range(words::PartOfSpeech::Verb), 70..120);
+        assert_eq!(words::ENGLISH.words[119], "discover");
+        assert_eq!(words::ENGLISH.part_of_speech(120), words::PartOfSpeech::Adjective);
+        assert_eq!(lang::DE.wordlist.words[lang::DE.wordlist.range(words::PartOfSpeech::Adverb).start], "immer");
+
+        let mut seed = [0u8; 32];
+        for (i, b) in seed.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37);
+        }
+        // Plain derivation is unchanged and still tags each word
+        let plain = words::derive_words(&seed, 50);
+        for i in 0..plain.count {
+            let idx = words::WORDLIST.iter().position(|w| w.as_bytes() == &plain.words[i][..plain.lens[i]]).unwrap();
+            assert_eq!(idx, ((seed[2 * i] as usize) << 8 | seed[2 * i + 1] as usize) % words::WORDLIST_SIZE);
+            assert_eq!(plain.parts[i], words::ENGLISH.part_of_speech(idx));
+        }
+
+        for (pack, version) in PACKS.iter().map(|&p| (p, words::WORDLIST_V1)).chain([(&lang::EN, words::WORDLIST_V2)]) {
+            let list = pack.wordlist_for(version);
+            for p in 0..words::PATTERNS.len() as u8 {
+                seed[20] = p;
+                let c = derive(&kinds(KIND_PATTERN_WORDS), pack, list, &seed, 50);
+                let Challenge::PatternWords { pattern, words: rw } = c else { panic!("{}: no pattern", pack.code) };
+                assert_eq!(pattern, p);
+                assert_eq!(rw.count, 8);
+                let parts = words::PATTERNS[p as usize];
+                for i in 0..rw.count {
+                    let w = &rw.words[i][..rw.lens[i]];
+                    let idx = list.words.iter().position(|x| x.as_bytes() == w).unwrap();
+                    assert_eq!(rw.parts[i], parts[i % parts.len()]);
+                    assert_eq!(list.part_of_speech(idx), rw.parts[i], "{}", pack.code);
+                    assert!((0..i).all(|j| &rw.words[j][..rw.lens[j]] != w), "{}: repeated word", pack.code);
+                }
+                assert_eq!(c.required_words().count, 8);
+            }
+        }
+
+        seed[20] = 0;
+        let c = derive(&kinds(KIND_PATTERN_WORDS), &lang::EN, lang::EN.wordlist, &seed, 8);
+        assert!(c.describe().contains(" (adjective), "), "{}", c.describe());
+    }
+
+    #[test]
+    fn test_acrostic() {
+        let (w, len) = word("store");
+        let ok = Challenge::Acrostic { word: w, len };
+        assert_eq!(ok.verify(&lang::EN, TEXT.as_bytes()), Ok(()));
+        assert!(ok.describe().contains("\"store\""));
+        let (w, len) = word("stone");
+        let bad = Challenge::Acrostic { word: w, len };
+        assert_eq!(bad.verify(&lang::EN, TEXT.as_bytes()), Err(ChallengeRejection::Acrostic));
+        // Too few sentences
+        let short = &TEXT[..TEXT.find("Really").unwrap()];
+        assert_eq!(ok.verify(&lang::EN, short.as_bytes()), Err(ChallengeRejection::Acrostic));
+    }
+
+    #[test]
+    fn test_sentence_start_and_question() {
+        let (w, len) = word("tonight");
+        assert_eq!(Challenge::SentenceStart { word: w, len }.verify(&lang::EN, TEXT.as_bytes()), Ok(()));
+        let (w, len) = word("river");
+        assert_eq!(Challenge::SentenceStart { word: w, len }.verify(&lang::EN, TEXT.as_bytes()), Err(ChallengeRejection::SentenceStart));
+
+        let (w, len) = word("weather");
+        assert_eq!(Challenge::QuestionWord { word: w, len }.verify(&lang::EN, TEXT.as_bytes()), Ok(()));
+        let (w, len) = word("river");
+        assert_eq!(Challenge::QuestionWord { word: w, len }.verify(&lang::EN, TEXT.as_bytes()), Err(ChallengeRejection::QuestionWord));
+        // Whole words only
+        let (w, len) = word("well");
+        assert_eq!(Challenge::QuestionWord { word: w, len }.verify(&lang::EN, TEXT.as_bytes()), Err(ChallengeRejection::QuestionWord));
+
+        // Accented words and opening punctuation
+        let es = "¿Dónde está el río que cruzamos ayer por la mañana?".as_bytes();
+        let (w, len) = word("dónde");
+        assert_eq!(Challenge::SentenceStart { word: w, len }.verify(&lang::ES, es), Ok(()));
+        let (w, len) = word("río");
+        assert_eq!(Challenge::QuestionWord { word: w, len }.verify(&lang::ES, es), Ok(()));
+    }
+
+    #[test]
+    fn test_sentence_parity() {
+        assert_eq!(Challenge::SentenceParity { even: false }.verify(&lang::EN, TEXT.as_bytes()), Ok(()));
+        assert_eq!(Challenge::SentenceParity { even: true }.verify(&lang::EN, TEXT.as_bytes()), Err(ChallengeRejection::SentenceParity));
+        // An unterminated tail doesn't count, nor do the extra dots of an ellipsis
+        let text = format!("{TEXT} And then");
+        assert_eq!(Challenge::SentenceParity { even: false }.verify(&lang::EN, text.as_bytes()), Ok(()));
+        let text = TEXT.replace("bridge.", "bridge...");
+        assert_eq!(Challenge::SentenceParity { even: false }.verify(&lang::EN, text.as_bytes()), Ok(()));
+    }
+
+    #[test]
+    fn test_category() {
+        // weather: rain, wind, storm, snow (+ "weather" itself isn't a member)
+        let weather = CATEGORIES.iter().position(|c| c.name == "weather").unwrap() as u8;
+        assert_eq!(Challenge::Category { index: weather, count: 4 }.verify(&lang::EN, TEXT.as_bytes()), Ok(()));
+        assert_eq!(Challenge::Category { index: weather, count: 5 }.verify(&lang::EN, TEXT.as_bytes()), Err(ChallengeRejection::Category));
+        // Repeats count once; "rainbow" is not "rain"
+        let text = "Rain, rain and more rain fell on the rainbow.";
+        assert_eq!(Challenge::Category { index: weather, count: 2 }.verify(&lang::EN, text.as_bytes()), Err(ChallengeRejection::Category));
+
+        for c in CATEGORIES.iter() {
+            assert!(c.words.len() <= 32, "{} has too many words for the bitmask", c.name);
+            assert!(c.words.iter().all(|w| w.bytes().all(|b| b.is_ascii_lowercase())), "{}", c.name);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Compact English dictionary for the word-ratio rule.
+//!
+//! A Bloom filter over `data/english-words.txt`, generated at build time by
+//! `build.rs`. Lookups are deterministic, allocation-free and no_std. A false
+//! positive (~0.2%) can only let a non-word count as a word.
+
+use crate::bloom::{bloom_indices, BLOOM_WORDS};
+
+include!(concat!(env!("OUT_DIR"), "/dictionary.rs"));
+
+/// Longest token looked up; longer tokens count as non-words.
+pub const MAX_TOKEN_LEN: usize = 24;
+
+/// Exact membership of a lowercase ASCII word.
+pub fn contains(bits: &[u64; BLOOM_WORDS], word: &[u8]) -> bool {
+    bloom_indices(word).iter().all(|&idx| bits[idx / 64] & (1 << (idx % 64)) != 0)
+}
+
+/// Membership of `word` or a base form with a regular suffix removed:
+/// -s, -es, -ies → -y, and -ed, -ing, -er, -est (restoring a dropped -e,
+/// -i → -y, or undoing a doubled final consonant), -ly.
+pub fn contains_inflected(bits: &[u64; BLOOM_WORDS], word: &[u8]) -> bool {
+    if word.len() > MAX_TOKEN_LEN {
+        return false;
+    }
+    if contains(bits, word) {
+        return true;
+    }
+    let n = word.len();
+    let mut buf = [0u8; MAX_TOKEN_LEN + 1];
+
+    for suffix in [&b"s"[..], b"es", b"ly"] {
+        if n >= suffix.len() + 2 && word.ends_with(suffix) && contains(bits, &word[..n - suffix.len()]) {
+            return true;
+        }
+    }
+    if n >= 5 && word.ends_with(b"ies") {
+        let base = n - 3;
+        buf[..base].copy_from_slice(&word[..base]);
+        buf[base] = b'y';
+        if contains(bits, &buf[..base + 1]) {
+            return true;
+        }
+    }
+    for suffix in [&b"ed"[..], b"ing", b"er", b"est"] {
+        if n < suffix.len() + 2 || !word.ends_with(suffix) {
+            continue;
+        }
+        let base = n - suffix.len();
+        let stem = &word[..base];
+        if contains(bits, stem) {
+            return true;
+        }
+        // hoped → hope
+        buf[..base].copy_from_slice(stem);
+        buf[base] = b'e';
+        if contains(bits, &buf[..base + 1]) {
+            return true;
+        }
+        // tried → try
+        if stem[base - 1] == b'i' {
+            buf[base - 1] = b'y';
+            if contains(bits, &buf[..base]) {
+                return true;
+            }
+        }
+        // stopped → stop
+        if base >= 3 && stem[base - 1] == stem[base - 2] && contains(bits, &stem[..base - 1]) {
+            return true;
+        }
+    }
+    false
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_words_present() {
+        let source = include_str!("../data/english-words.txt");
+        let mut count = 0;
+        for word in source.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            assert!(contains(&ENGLISH_BLOOM, word.as_bytes()), "{word} missing from filter");
+            count += 1;
+        }
+        assert_eq!(count, ENGLISH_WORD_COUNT);
+    }
+
+    #[test]
+    fn test_inflections() {
+        for word in ["walked", "hoped", "tried", "stopped", "running", "cities", "boxes", "quickly", "stronger", "largest"] {
+            assert!(contains_inflected(&ENGLISH_BLOOM, word.as_bytes()), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_non_words() {
+        let misses = ["zavi", "thenor", "kelon", "pavelin", "xqzt", "consectetur", "lorem", "ipsum"]
+            .iter()
+            .filter(|w| !contains_inflected(&ENGLISH_BLOOM, w.as_bytes()))
+            .count();
+        assert!(misses >= 7, "only {misses} of 8 non-words rejected");
+    }
+}
@@ -0,0 +1,49 @@
+//! keccak256 for word derivation and the PoW hash: the runtime syscall on
+//! Solana (as `solana_program::keccak` does), the `sha3` crate elsewhere.
+
+/// keccak256 of the concatenation of `vals`.
+#[cfg(not(target_os = "solana"))]
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    use sha3::{Digest, Keccak256};
+
+    let mut hasher = Keccak256::new();
+    for val in vals {
+        hasher.update(val);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_keccak256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+}
+
+/// keccak256 of the concatenation of `vals`.
+#[cfg(target_os = "solana")]
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    // SAFETY: the syscall reads `vals` as (ptr, len) pairs, which is the
+    // layout of `&[u8]`, and writes 32 bytes to `hash`.
+    unsafe {
+        sol_keccak256(vals as *const _ as *const u8, vals.len() as u64, hash.as_mut_ptr());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashv() {
+        // keccak256("") and the split/joined equivalence
+        assert_eq!(
+            hashv(&[]),
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+                0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+            ]
+        );
+        assert_eq!(hashv(&[b"poi", b"-words"]), hashv(&[b"poi-words"]));
+    }
+}
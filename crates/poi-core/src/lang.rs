@@ -0,0 +1,399 @@
+//! Language packs for text verification.
+//!
+//! A pack is everything `verify_text` and `derive_words` need to know about a
+//! language: which letters count as vowels, which five bigrams must be common,
+//! the wordlist required words are drawn from, and which non-ASCII characters
+//! are allowed. Non-ASCII support is limited to two-byte UTF-8 (U+0080–U+07FF),
+//! which covers accented Latin scripts.
+//!
+//! `TextRules::languages` enables packs; `select` picks one per epoch from the
+//! challenge seed. A mask of 0 means English only, as before packs existed.
+
+use crate::bloom::BLOOM_WORDS;
+use crate::dictionary;
+use crate::words::{self, PackedWordlist, WordSource, Wordlist};
+
+/// `TextRules::languages` bits.
+pub const LANG_EN: u8 = 1 << 0;
+pub const LANG_ES: u8 = 1 << 1;
+pub const LANG_FR: u8 = 1 << 2;
+pub const LANG_DE: u8 = 1 << 3;
+pub const LANG_ALL: u8 = LANG_EN | LANG_ES | LANG_FR | LANG_DE;
+
+pub struct LanguagePack {
+    pub code: &'static str,                 // ISO 639-1
+    pub accented_vowels: &'static [u16],    // lowercase code points counted as vowels
+    pub accented_consonants: &'static [u16], // lowercase code points counted as consonants
+    pub punctuation: &'static [u16],        // non-ASCII marks allowed outside words
+    pub bigrams: [[u8; 2]; 5],              // lowercase ASCII; each must be common
+    pub wordlist: &'static Wordlist,        // lowercase, ≤ words::MAX_WORD_LEN bytes
+    pub model: Option<&'static FrequencyModel>, // letter/trigram statistics; None skips those rules
+    pub dictionary: Option<&'static [u64; BLOOM_WORDS]>, // Bloom filter of real words; None skips the word-ratio rule
+    pub inflections: bool,                  // required words may take English suffixes (see verify)
+}
+
+/// Reference statistics for the letter-frequency and trigram rules.
+pub struct FrequencyModel {
+    pub letters: [u16; 26],            // per 10 000 letters, a–z
+    pub trigrams: &'static [[u8; 3]],  // common trigrams over letters only (spaces skipped), sorted
+}
+
+/// Letter frequencies from large English corpora; trigrams are the ~50 most
+/// common, which make up roughly a quarter of all trigrams in English prose.
+pub const ENGLISH_MODEL: FrequencyModel = FrequencyModel {
+    letters: [
+        817, 149, 278, 425, 1270, 223, 202, 609, 697, 15, 77, 403, 241, // a–m
+        675, 751, 193, 10, 599, 633, 906, 276, 98, 236, 15, 197, 7,     // n–z
+    ],
+    trigrams: &[
+        *b"all", *b"and", *b"are", *b"ate", *b"ati", *b"ave", *b"con", *b"dth", *b"ear", *b"edt",
+        *b"ent", *b"ere", *b"ers", *b"est", *b"eth", *b"eve", *b"for", *b"fth", *b"hat", *b"her",
+        *b"hes", *b"his", *b"ing", *b"int", *b"ion", *b"ith", *b"men", *b"nce", *b"ndt", *b"nth",
+        *b"oft", *b"ons", *b"ont", *b"oth", *b"oul", *b"rea", *b"res", *b"sin", *b"sta", *b"sth",
+        *b"sto", *b"ted", *b"ter", *b"tha", *b"the", *b"thi", *b"tin", *b"tio", *b"tth", *b"ver",
+        *b"was", *b"wit",
+    ],
+};
+
+/// Packs in `TextRules::languages` bit order.
+pub const PACKS: [&LanguagePack; 4] = [&EN, &ES, &FR, &DE];
+
+pub const EN: LanguagePack = LanguagePack {
+    code: "en",
+    accented_vowels: &[],
+    accented_consonants: &[],
+    punctuation: &[],
+    bigrams: [*b"th", *b"he", *b"in", *b"er", *b"an"],
+    wordlist: &words::ENGLISH,
+    model: Some(&ENGLISH_MODEL),
+    dictionary: Some(&dictionary::ENGLISH_BLOOM),
+    inflections: true,
+};
+
+pub const ES: LanguagePack = LanguagePack {
+    code: "es",
+    // á é í ó ú ü
+    accented_vowels: &[0xE1, 0xE9, 0xED, 0xF3, 0xFA, 0xFC],
+    // ñ
+    accented_consonants: &[0xF1],
+    // ¡ ¿ « »
+    punctuation: &[0xA1, 0xBF, 0xAB, 0xBB],
+    bigrams: [*b"de", *b"en", *b"es", *b"el", *b"la"],
+    wordlist: &WORDS_ES,
+    model: None,
+    dictionary: None,
+    inflections: false,
+};
+
+pub const FR: LanguagePack = LanguagePack {
+    code: "fr",
+    // à â é è ê ë î ï ô ù û ü ÿ œ æ
+    accented_vowels: &[0xE0, 0xE2, 0xE9, 0xE8, 0xEA, 0xEB, 0xEE, 0xEF, 0xF4, 0xF9, 0xFB, 0xFC, 0xFF, 0x153, 0xE6],
+    // ç
+    accented_consonants: &[0xE7],
+    // « »
+    punctuation: &[0xAB, 0xBB],
+    bigrams: [*b"es", *b"le", *b"de", *b"en", *b"re"],
+    wordlist: &WORDS_FR,
+    model: None,
+    dictionary: None,
+    inflections: false,
+};
+
+pub const DE: LanguagePack = LanguagePack {
+    code: "de",
+    // ä ö ü
+    accented_vowels: &[0xE4, 0xF6, 0xFC],
+    // ß
+    accented_consonants: &[0xDF],
+    // « »
+    punctuation: &[0xAB, 0xBB],
+    bigrams: [*b"en", *b"er", *b"ch", *b"de", *b"ie"],
+    wordlist: &WORDS_DE,
+    model: None,
+    dictionary: None,
+    inflections: false,
+};
+
+/// Pack for this epoch: one of the enabled packs, chosen by the last seed byte
+/// (`derive_words` reads from the front). Mask 0 is English.
+pub fn select(languages: u8, seed: &[u8; 32]) -> &'static LanguagePack {
+    let enabled = languages & LANG_ALL;
+    if enabled == 0 {
+        return &EN;
+    }
+    let mut n = seed[31] % enabled.count_ones() as u8;
+    let mut i = 0;
+    while i < PACKS.len() {
+        if enabled & (1 << i) != 0 {
+            if n == 0 {
+                return PACKS[i];
+            }
+            n -= 1;
+        }
+        i += 1;
+    }
+    &EN
+}
+
+/// Decode a two-byte UTF-8 sequence (U+0080–U+07FF).
+#[inline(always)]
+pub fn decode2(lead: u8, cont: u8) -> Option<u16> {
+    if (0xC2..=0xDF).contains(&lead) && (0x80..=0xBF).contains(&cont) {
+        Some((((lead & 0x1F) as u16) << 6) | (cont & 0x3F) as u16)
+    } else {
+        None
+    }
+}
+
+/// Lowercase a code point in Latin-1 or Œ. Both stay within the same UTF-8
+/// lead byte, so byte-wise matching only needs the continuation byte lowered.
+#[inline(always)]
+pub fn lower_cp(cp: u16) -> u16 {
+    match cp {
+        0xC0..=0xDE if cp != 0xD7 => cp + 0x20,
+        0x152 => 0x153,
+        _ => cp,
+    }
+}
+
+/// Uppercase letter under `lower_cp` (ß has no uppercase form here).
+#[inline(always)]
+pub fn is_upper_cp(cp: u16) -> bool {
+    lower_cp(cp) != cp
+}
+
+/// What a non-ASCII code point is in this pack.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    Vowel,
+    Consonant,
+    Punctuation,
+    Disallowed,
+}
+
+impl LanguagePack {
+    /// Wordlist for `MineState::wordlist_version`. Only English is versioned;
+    /// other packs always use their own list.
+    pub fn wordlist_for(&self, version: u8) -> &'static Wordlist {
+        if self.code == EN.code {
+            words::english(version)
+        } else {
+            self.wordlist
+        }
+    }
+
+    /// List required words are drawn from: the active on-chain WordList for
+    /// English, otherwise `wordlist_for(version)`.
+    pub fn active_wordlist<'a>(&self, version: u8, on_chain: Option<&'a PackedWordlist<'a>>) -> &'a dyn WordSource {
+        match on_chain {
+            Some(list) if self.code == EN.code => list,
+            _ => self.wordlist_for(version),
+        }
+    }
+
+    /// Accented letter (vowel or consonant) in this pack.
+    #[inline(always)]
+    pub fn is_letter(&self, cp: u16) -> bool {
+        matches!(self.classify(cp), CharClass::Vowel | CharClass::Consonant)
+    }
+
+    pub fn classify(&self, cp: u16) -> CharClass {
+        let lower = lower_cp(cp);
+        if self.accented_vowels.contains(&lower) {
+            CharClass::Vowel
+        } else if self.accented_consonants.contains(&lower) {
+            CharClass::Consonant
+        } else if self.punctuation.contains(&cp) {
+            CharClass::Punctuation
+        } else {
+            CharClass::Disallowed
+        }
+    }
+}
+
+pub const WORDS_ES: Wordlist = Wordlist::new(&WORDLIST_ES, [55, 20, 27, 8]);
+
+pub const WORDLIST_ES: [&str; 110] = [
+    // Sustantivos
+    "tiempo","vida","mundo","lugar","agua","casa","música","poder","sueño","corazón",
+    "tierra","océano","nube","piedra","llama","noche","campo","espacio","verdad","tormenta",
+    "torre","planta","metal","vidrio","rueda","puente","bosque","jardín","mercado","isla",
+    "desierto","plata","sombra","energía","futuro","memoria","momento","invierno","verano","señal",
+    "sistema","diseño","método","razón","carta","persona","animal","flor","mañana","viaje",
+    "historia","cultura","libertad","camino","silencio",
+    // Verbos
+    "pensar","aprender","escribir","hablar","bailar","subir","mirar","brillar","llevar","pintar",
+    "enseñar","resolver","confiar","guiar","crear","seguir","escuchar","viajar","buscar","volver",
+    // Adjetivos
+    "claro","suave","fuerte","simple","oculto","dorado","helado","amargo","tierno","sutil",
+    "humilde","firme","honesto","sagrado","único","activo","nativo","estrecho","líquido","remoto",
+    "antiguo","moderno","natural","perfecto","extraño","curioso","lejano",
+    // Adverbios
+    "siempre","nunca","despacio","apenas","quizás","después","temprano","todavía",
+];
+
+pub const WORDS_FR: Wordlist = Wordlist::new(&WORDLIST_FR, [55, 20, 27, 8]);
+
+pub const WORDLIST_FR: [&str; 110] = [
+    // Noms
+    "temps","monde","lieu","maison","musique","pouvoir","rêve","cœur","terre","océan",
+    "rivière","nuage","pierre","flamme","voix","nuit","champ","espace","vérité","paix",
+    "tempête","plante","métal","verre","roue","pont","forêt","jardin","marché","désert",
+    "argent","ombre","esprit","avenir","mémoire","moment","saison","hiver","signal","système",
+    "méthode","raison","réponse","lettre","personne","animal","fleur","matin","soir","voyage",
+    "histoire","culture","liberté","chemin","silence",
+    // Verbes
+    "penser","écrire","parler","danser","grimper","regarder","briller","porter","peindre","chercher",
+    "suivre","écouter","voyager","créer","partager","guider","devenir","rester","trouver","explorer",
+    // Adjectifs
+    "clair","calme","doux","fort","simple","caché","doré","glacé","amer","tendre",
+    "subtil","humble","stable","honnête","brisé","sacré","unique","actif","lisse","étroit",
+    "liquide","lointain","ancien","moderne","naturel","spécial","parfait",
+    // Adverbes
+    "souvent","jamais","toujours","presque","encore","bientôt","ensemble","ailleurs",
+];
+
+pub const WORDS_DE: Wordlist = Wordlist::new(&WORDLIST_DE, [55, 20, 27, 8]);
+
+pub const WORDLIST_DE: [&str; 110] = [
+    // Substantive
+    "zeit","leben","welt","platz","wasser","licht","haus","musik","kraft","traum",
+    "herz","erde","ozean","fluss","wolke","stein","flamme","stimme","nacht","feld",
+    "raum","wahrheit","frieden","sturm","turm","pflanze","metall","glas","brücke","wald",
+    "garten","markt","insel","wüste","silber","schatten","geist","energie","zukunft","winter",
+    "sommer","signal","system","methode","grund","antwort","brief","person","tier","blume",
+    "morgen","abend","reise","straße","freiheit",
+    // Verben
+    "denken","lernen","bauen","sprechen","tanzen","klettern","schauen","tragen","malen","lehren",
+    "suchen","folgen","hören","reisen","finden","wandern","sammeln","teilen","führen","bleiben",
+    // Adjektive
+    "hell","ruhig","sanft","stark","einfach","golden","still","gefroren","bitter","zart",
+    "klar","ehrlich","heilig","einzig","aktiv","glatt","schmal","sozial","sicher","stabil",
+    "modern","digital","zentral","privat","perfekt","seltsam","fern",
+    // Adverbien
+    "immer","langsam","kaum","wieder","früh","später","heute","schon",
+];
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::{verify_text_in, TextRejection, TextRules};
+
+    #[test]
+    fn test_wordlists() {
+        for pack in PACKS {
+            let list = pack.wordlist_for(words::LATEST_WORDLIST);
+            for (i, w) in list.words.iter().enumerate() {
+                assert!(w.chars().count() >= 4, "{}: {w} too short", pack.code);
+                assert!(w.len() <= words::MAX_WORD_LEN, "{}: {w} over {} bytes", pack.code, words::MAX_WORD_LEN);
+                assert!(!list.words[..i].contains(w), "{}: {w} duplicated", pack.code);
+                for c in w.chars() {
+                    if c.is_ascii() {
+                        assert!(c.is_ascii_lowercase(), "{}: {w} not lowercase ASCII", pack.code);
+                    } else {
+                        let class = pack.classify(c as u16);
+                        assert!(class == CharClass::Vowel || class == CharClass::Consonant, "{}: {w} has {c}", pack.code);
+                        assert_eq!(lower_cp(c as u16), c as u16, "{}: {w} not lowercase", pack.code);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_english_model() {
+        let total: u32 = ENGLISH_MODEL.letters.iter().map(|&p| p as u32).sum();
+        assert!((9900..=10100).contains(&total), "letter table sums to {total}");
+        assert!(ENGLISH_MODEL.trigrams.windows(2).all(|w| w[0] < w[1]), "trigrams must be sorted and unique");
+    }
+
+    #[test]
+    fn test_select() {
+        let mut seed = [0u8; 32];
+        assert_eq!(select(0, &seed).code, "en");
+        assert_eq!(select(LANG_DE, &seed).code, "de");
+
+        // Seed's last byte walks the enabled packs in bit order
+        let mask = LANG_ES | LANG_DE;
+        seed[31] = 0;
+        assert_eq!(select(mask, &seed).code, "es");
+        seed[31] = 1;
+        assert_eq!(select(mask, &seed).code, "de");
+        seed[31] = 2;
+        assert_eq!(select(mask, &seed).code, "es");
+    }
+
+    #[test]
+    fn test_decode() {
+        // é = C3 A9, É = C3 89, œ = C5 93, Œ = C5 92
+        assert_eq!(decode2(0xC3, 0xA9), Some(0xE9));
+        assert_eq!(lower_cp(decode2(0xC3, 0x89).unwrap()), 0xE9);
+        assert_eq!(lower_cp(decode2(0xC5, 0x92).unwrap()), 0x153);
+        assert!(is_upper_cp(0xC4) && !is_upper_cp(0xDF) && !is_upper_cp(0xD7));
+        // Overlong and bad continuation
+        assert_eq!(decode2(0xC0, 0x80), None);
+        assert_eq!(decode2(0xC3, 0x41), None);
+    }
+
+    const SPANISH: &str = "La mañana era tranquila y el cielo tenía un color azul muy claro sobre las montañas del norte. \
+        ¿Alguna vez has pensado en la historia de los pueblos antiguos que vivieron cerca del río durante siglos? \
+        Los niños jugaban en la plaza mientras sus padres hablaban de la cosecha, del tiempo y de las noticias que llegaban desde la ciudad. \
+        El viento movía las hojas. \
+        Después de la comida, la familia caminó por el jardín y miró cómo el sol se escondía lentamente detrás de los árboles.";
+
+    const FRENCH: &str = "Le matin était calme et le ciel avait une couleur bleue très claire au-dessus des montagnes du nord. \
+        Avez-vous déjà pensé à l'histoire des anciens villages qui vivaient près de la rivière pendant des siècles? \
+        Les enfants jouaient sur la place pendant que leurs parents parlaient de la récolte, du temps et des nouvelles qui arrivaient de la ville. \
+        Le vent agitait les feuilles. \
+        Après le repas, la famille a marché dans le jardin et a regardé le soleil se cacher lentement derrière les arbres.";
+
+    const GERMAN: &str = "Der Morgen war ruhig und der Himmel hatte eine sehr klare blaue Farbe über den Bergen im Norden. \
+        Hast du jemals über die Geschichte der alten Dörfer nachgedacht, die seit Jahrhunderten in der Nähe des Flusses liegen? \
+        Die Kinder spielten auf dem Platz, während ihre Eltern über die Ernte, das Wetter und die Nachrichten aus der großen Stadt redeten. \
+        Der Wind bewegte die Blätter. \
+        Nach dem Essen ging die Familie durch den Garten und sah, wie die Sonne langsam hinter den Bäumen verschwand.";
+
+    #[test]
+    fn test_spanish() {
+        let words: &[&[u8]] = &["mañana".as_bytes(), b"historia", "jardín".as_bytes()];
+        assert_eq!(verify_text_in(&ES, SPANISH.as_bytes(), words, &TextRules::DEFAULT), Ok(()));
+        // No model: the English statistics don't apply
+        let english = TextRules { max_letter_chi2: 150, min_trigram_pct: 15, ..TextRules::DEFAULT };
+        assert_eq!(verify_text_in(&ES, SPANISH.as_bytes(), words, &english), Ok(()));
+        // Same text is not English
+        assert_eq!(verify_text_in(&EN, SPANISH.as_bytes(), &[], &TextRules::DEFAULT), Err(TextRejection::NonAscii));
+        // ¿ may open a sentence under V2
+        assert_ne!(verify_text_in(&ES, SPANISH.as_bytes(), &[], &TextRules::PUBLISHED), Err(TextRejection::SentenceStructure));
+    }
+
+    #[test]
+    fn test_french() {
+        let words: &[&[u8]] = &[b"calme", "rivière".as_bytes(), b"jardin"];
+        assert_eq!(verify_text_in(&FR, FRENCH.as_bytes(), words, &TextRules::DEFAULT), Ok(()));
+        // ß is German only
+        let text = FRENCH.replacen("calme", "calße", 1);
+        assert_eq!(verify_text_in(&FR, text.as_bytes(), &[], &TextRules::DEFAULT), Err(TextRejection::NonAscii));
+    }
+
+    #[test]
+    fn test_german() {
+        // Case-insensitive across umlauts: "Nähe" matches, capitalised nouns match
+        let words: &[&[u8]] = &[b"morgen", "nähe".as_bytes(), b"garten"];
+        assert_eq!(verify_text_in(&DE, GERMAN.as_bytes(), words, &TextRules::DEFAULT), Ok(()));
+        // English bigrams are not German bigrams
+        let de_chars = LanguagePack { bigrams: EN.bigrams, ..DE };
+        assert_eq!(verify_text_in(&de_chars, GERMAN.as_bytes(), &[], &TextRules::DEFAULT), Err(TextRejection::Bigram));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut text = GERMAN.as_bytes().to_vec();
+        // Truncate ü (C3 BC) to a bare lead byte
+        let pos = text.iter().position(|&b| b == 0xBC).unwrap();
+        text[pos] = b' ';
+        assert_eq!(verify_text_in(&DE, &text, &[], &TextRules::DEFAULT), Err(TextRejection::NonAscii));
+    }
+}
@@ -0,0 +1,19 @@
+//! Consensus rules of Proof of Inference, shared by the on-chain program and
+//! off-chain tools: the text verifier and challenge kinds, word derivation,
+//! the PoW difficulty check and the emission schedule.
+//!
+//! no_std (with `alloc` for challenge descriptions) and free of Anchor, so a
+//! miner or indexer can depend on exactly the code the program runs.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+mod bloom;
+pub mod challenge;
+pub mod dictionary;
+pub mod keccak;
+pub mod lang;
+pub mod mining;
+pub mod verify;
+pub mod words;
@@ -0,0 +1,122 @@
+//! Proof of work and emission: the PoW hash and difficulty check, the halving
+//! reward schedule and linear vesting.
+
+use alloc::vec::Vec;
+
+use crate::keccak;
+
+pub const MAX_SUPPLY: u64 = 100_000_000_000_000;               // 100B × 10^3 (3 decimals)
+pub const INITIAL_REWARD: u64 = 25_000_000;                    // 25K CRB × 10^3
+pub const HALVING_INTERVAL: u64 = 2_000_000;
+pub const VESTING_DURATION: i64 = 30 * 24 * 3600;              // 30 days in seconds
+
+/// Reward with halving: INITIAL_REWARD >> (total_mined / HALVING_INTERVAL)
+pub fn calculate_reward(total_mined: u64) -> u64 {
+    let halvings = total_mined / HALVING_INTERVAL;
+    if halvings >= 64 {
+        return 0;
+    }
+    INITIAL_REWARD >> halvings
+}
+
+/// Separator between the text and the nonce in the PoW preimage.
+pub const POW_SEPARATOR: &[u8] = b"||";
+
+/// PoW preimage: challenge_seed ‖ miner ‖ text ‖ "||" ‖ nonce (u64 LE).
+pub fn pow_preimage(seed: &[u8; 32], miner: &[u8; 32], text: &[u8], nonce: u64) -> Vec<u8> {
+    let mut preimage = Vec::with_capacity(32 + 32 + text.len() + POW_SEPARATOR.len() + 8);
+    preimage.extend_from_slice(seed);
+    preimage.extend_from_slice(miner);
+    preimage.extend_from_slice(text);
+    preimage.extend_from_slice(POW_SEPARATOR);
+    preimage.extend_from_slice(&nonce.to_le_bytes());
+    preimage
+}
+
+/// keccak256 of `pow_preimage`, hashed in place without building it.
+pub fn pow_hash(seed: &[u8; 32], miner: &[u8; 32], text: &[u8], nonce: u64) -> [u8; 32] {
+    keccak::hashv(&[seed, miner, text, POW_SEPARATOR, &nonce.to_le_bytes()])
+}
+
+/// Check that hash has at least `difficulty` leading zero bits.
+pub fn check_difficulty(hash: &[u8; 32], difficulty: u64) -> bool {
+    let full_bytes = (difficulty / 8) as usize;
+    let remaining_bits = (difficulty % 8) as u8;
+
+    if full_bytes > 32 || hash[..full_bytes].iter().any(|&b| b != 0) {
+        return false;
+    }
+
+    if remaining_bits > 0 && full_bytes < 32 {
+        let mask = 0xFF << (8 - remaining_bits);
+        if hash[full_bytes] & mask != 0 {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Drip vesting: move locked → unlocked based on elapsed time.
+pub fn drip_vesting(locked: &mut u64, unlocked: &mut u64, last_update: &mut i64, now: i64) {
+    let release = vested_amount(*locked, *last_update, now);
+    *unlocked += release;
+    *locked -= release;
+    *last_update = now;
+}
+
+/// Amount of `locked` that has vested between `last_update` and `now`.
+pub fn vested_amount(locked: u64, last_update: i64, now: i64) -> u64 {
+    if locked == 0 || now <= last_update {
+        return 0;
+    }
+    let elapsed = now - last_update;
+    if elapsed >= VESTING_DURATION {
+        locked
+    } else {
+        // Use u128 to avoid overflow
+        (locked as u128 * elapsed as u128 / VESTING_DURATION as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_difficulty() {
+        let mut hash = [0xFFu8; 32];
+        assert!(check_difficulty(&hash, 0));
+        assert!(!check_difficulty(&hash, 1));
+        hash[0] = 0;
+        hash[1] = 0x1F;
+        assert!(check_difficulty(&hash, 11));
+        assert!(!check_difficulty(&hash, 12));
+        assert!(check_difficulty(&[0; 32], 256));
+        assert!(!check_difficulty(&[0; 32], 264));
+    }
+
+    #[test]
+    fn test_pow_hash() {
+        let (seed, miner) = ([7u8; 32], [9u8; 32]);
+        let preimage = pow_preimage(&seed, &miner, b"text", 0x0102);
+        assert_eq!(preimage.len(), 32 + 32 + 4 + 2 + 8);
+        assert_eq!(&preimage[64..], b"text||\x02\x01\0\0\0\0\0\0");
+        assert_eq!(pow_hash(&seed, &miner, b"text", 0x0102), keccak::hashv(&[&preimage]));
+    }
+
+    #[test]
+    fn test_reward_and_vesting() {
+        assert_eq!(calculate_reward(0), INITIAL_REWARD);
+        assert_eq!(calculate_reward(HALVING_INTERVAL), INITIAL_REWARD / 2);
+        assert_eq!(calculate_reward(64 * HALVING_INTERVAL), 0);
+
+        let (mut locked, mut unlocked, mut last_update) = (1_000, 0, 0);
+        drip_vesting(&mut locked, &mut unlocked, &mut last_update, VESTING_DURATION / 4);
+        assert_eq!((locked, unlocked, last_update), (750, 250, VESTING_DURATION / 4));
+        // Time going backwards releases nothing
+        assert_eq!(vested_amount(locked, last_update, 0), 0);
+        drip_vesting(&mut locked, &mut unlocked, &mut last_update, 10 * VESTING_DURATION);
+        assert_eq!((locked, unlocked), (0, 1_000));
+    }
+}
@@ -0,0 +1,1330 @@
+//! Text constraint verification for Proof of Inference.
+//!
+//! Single O(n) pass, no_std compatible, zero heap allocation.
+//! Checks: length, required words (with word boundaries), sentence structure,
+//! vowel/space ratios, consonant clusters, bigram frequency, byte diversity.
+//! Letters, vowels and bigrams come from a `lang::LanguagePack` (English by default);
+//! packs with a `FrequencyModel` also get letter chi-squared and trigram coverage checks,
+//! and packs with a dictionary a minimum share of real words.
+//! Thresholds come from `TextRules`; `TextRules::DEFAULT` is the original preset,
+//! `TextRules::PUBLISHED` the rule set the README documents.
+
+use crate::challenge;
+use crate::dictionary::{self, MAX_TOKEN_LEN};
+use crate::lang::{self, CharClass, LanguagePack};
+use crate::words;
+
+/// Hard ceiling for `TextRules::max_len` (Solana tx limit is 1232 bytes; ~900 usable for text).
+pub const MAX_TEXT_LEN: u16 = 900;
+
+/// Rule set revisions. A stored `version` of 0 predates the field and means V1.
+/// V1: the checks the verifier has always run.
+pub const RULES_V1: u8 = 1;
+/// V2: adds capitalised, terminated sentences and bigram counts ≥ len / 80.
+pub const RULES_V2: u8 = 2;
+/// Newest revision `TextRules::is_valid` accepts.
+pub const LATEST_RULES_VERSION: u8 = RULES_V2;
+
+/// Sentence dedup table size (fixed, no heap). A sentence is at least 5 words
+/// (≥ 10 bytes), so a maximum-length text can't fill it.
+const DEDUP_SLOTS: usize = 128;
+const _: () = assert!(DEDUP_SLOTS > MAX_TEXT_LEN as usize / 10);
+
+/// Largest `TextRules::near_duplicate_bits`. The index checks one shard per
+/// fingerprint block and uses `near_duplicate_bits + 1` blocks, so this caps
+/// the shard accounts a submit passes at 8.
+pub const MAX_NEAR_DUPLICATE_BITS: u8 = 7;
+
+/// Words per shingle for the SimHash fingerprint. Two keeps a one-word edit
+/// within a few bits; three spreads it over too many.
+const SIMHASH_SHINGLE_WORDS: usize = 2;
+
+/// Words per shingle for the repeated-phrase rule.
+const SHINGLE_WORDS: u32 = 3;
+/// Shingle table size. Words take ≥ 2 bytes with their separator.
+const SHINGLE_SLOTS: usize = 512;
+const _: () = assert!(SHINGLE_SLOTS > MAX_TEXT_LEN as usize / 2);
+
+/// Verifier thresholds. Stored on-chain so they can be tuned per deployment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct TextRules {
+    pub min_len: u16,              // bytes
+    pub max_len: u16,              // bytes, ≤ MAX_TEXT_LEN
+    pub min_sentence_words: u8,    // every sentence
+    pub max_sentence_words: u8,    // every sentence
+    pub short_sentence_words: u8,  // need one sentence with ≤ this many words
+    pub long_sentence_words: u8,   // need one sentence with ≥ this many words
+    pub min_sentences: u8,
+    pub min_questions: u8,
+    pub min_vowel_pct: u8,         // of letters
+    pub max_vowel_pct: u8,
+    pub min_space_pct: u8,         // of bytes
+    pub max_space_pct: u8,
+    pub max_consonant_run: u8,
+    pub max_avg_cluster_x10: u8,   // average consonant cluster must be < this / 10
+    pub min_bigram_count: u8,      // each of th/he/in/er/an
+    pub min_unique_bytes: u8,
+    pub min_word_gap: u16,         // bytes between consecutive required words
+    pub scale_with_difficulty: bool, // tighten per difficulty tier (see `for_difficulty`)
+    pub version: u8,               // RULES_V1 / RULES_V2; 0 = V1
+    pub languages: u8,             // lang::LANG_* packs the seed picks from; 0 = English
+    pub max_letter_chi2: u16,      // letter frequency chi-squared vs the pack model; 0 = off
+    pub min_trigram_pct: u8,       // % of letter trigrams among the model's common ones; 0 = off
+    pub min_dictionary_pct: u8,    // % of tokens found in the pack dictionary; 0 = off
+    pub max_repeated_shingle_pct: u8, // % of 3-word shingles repeating an earlier one; 0 = off
+    pub fingerprint_shards: u8,    // shards per block of the epoch SimHash index; 0 = index off
+    pub near_duplicate_bits: u8,   // reject fingerprints within this Hamming distance (≤ MAX_NEAR_DUPLICATE_BITS)
+    pub inflect_required_words: bool, // required words match with English suffixes (-s, -ed, -ing, …)
+    pub challenge_kinds: u8,       // challenge::KIND_* bitmask picked from per epoch; 0 = required words only
+    pub word_derivation: u8,       // words::DERIVATION_V1 / DERIVATION_V2; 0 = V1
+    pub extra_words: u8,           // required words on top of the tier's count (V2 only)
+}
+
+impl TextRules {
+    /// Serialized size in bytes (Borsh, as stored in `MineState`).
+    pub const SIZE: usize = 34;
+
+    /// Thresholds the verifier has always used.
+    pub const DEFAULT: TextRules = TextRules {
+        min_len: 256,
+        max_len: 800,
+        min_sentence_words: 5,
+        max_sentence_words: 35,
+        short_sentence_words: 10,
+        long_sentence_words: 20,
+        min_sentences: 2,
+        min_questions: 1,
+        min_vowel_pct: 30,
+        max_vowel_pct: 48,
+        min_space_pct: 12,
+        max_space_pct: 22,
+        max_consonant_run: 5,
+        max_avg_cluster_x10: 25,
+        min_bigram_count: 2,
+        min_unique_bytes: 28,
+        min_word_gap: 40,
+        scale_with_difficulty: false,
+        version: RULES_V1,
+        languages: 0,
+        max_letter_chi2: 0,
+        min_trigram_pct: 0,
+        min_dictionary_pct: 0,
+        max_repeated_shingle_pct: 0,
+        fingerprint_shards: 0,
+        near_duplicate_bits: 0,
+        inflect_required_words: false,
+        challenge_kinds: 0,
+        word_derivation: words::DERIVATION_V1,
+        extra_words: 0,
+    };
+
+    /// The published spec (README / MINING.md): V2 checks, at least 3 sentences,
+    /// English letter and trigram statistics, 70% dictionary words, at most 10%
+    /// repeated phrases.
+    pub const PUBLISHED: TextRules = TextRules {
+        min_sentences: 3,
+        version: RULES_V2,
+        max_letter_chi2: 150,
+        min_trigram_pct: 15,
+        min_dictionary_pct: 70,
+        max_repeated_shingle_pct: 10,
+        ..TextRules::DEFAULT
+    };
+
+    /// Rules in force at `difficulty`.
+    ///
+    /// With `scale_with_difficulty` off these are the rules unchanged. With it on,
+    /// each tier of `words::difficulty_tier` (0–5) adds, on top of these rules:
+    ///
+    /// | Per tier             | Tier 5 on DEFAULT |
+    /// |----------------------|-------------------|
+    /// | +32 bytes min length | 416 bytes         |
+    /// | +1 sentence / 2 tiers| 4 sentences       |
+    /// | +1 question / 3 tiers| 2 questions       |
+    /// | +8 bytes word gap    | 80 bytes          |
+    /// | +1 bigram / 2 tiers  | 4 each            |
+    ///
+    /// Tier 0 (difficulty ≤ 10) is always the rules unchanged.
+    pub fn for_difficulty(&self, difficulty: u64) -> TextRules {
+        if !self.scale_with_difficulty {
+            return *self;
+        }
+        let tier = words::difficulty_tier(difficulty);
+        TextRules {
+            min_len: self.min_len.saturating_add(32 * tier as u16).min(self.max_len),
+            min_sentences: self.min_sentences.saturating_add(tier / 2),
+            min_questions: self.min_questions.saturating_add(tier / 3),
+            min_word_gap: self.min_word_gap.saturating_add(8 * tier as u16),
+            min_bigram_count: self.min_bigram_count.saturating_add(tier / 2),
+            ..*self
+        }
+    }
+
+    /// Internally consistent and within what a transaction can carry.
+    pub fn is_valid(&self) -> bool {
+        self.min_len <= self.max_len
+            && self.max_len <= MAX_TEXT_LEN
+            && self.min_sentence_words > 0
+            && self.min_sentence_words <= self.max_sentence_words
+            && self.short_sentence_words <= self.long_sentence_words
+            && self.min_vowel_pct <= self.max_vowel_pct
+            && self.max_vowel_pct <= 100
+            && self.min_space_pct <= self.max_space_pct
+            && self.max_space_pct <= 100
+            && self.min_unique_bytes <= 128
+            && self.version <= LATEST_RULES_VERSION
+            && self.languages & !lang::LANG_ALL == 0
+            && self.min_trigram_pct <= 100
+            && self.min_dictionary_pct <= 100
+            && self.max_repeated_shingle_pct <= 100
+            && self.near_duplicate_bits <= MAX_NEAR_DUPLICATE_BITS
+            && self.challenge_kinds & !challenge::KIND_ALL == 0
+            && self.word_derivation <= words::LATEST_DERIVATION
+            && (self.extra_words == 0 || self.word_derivation >= words::DERIVATION_V2)
+            && self.extra_words as usize <= words::MAX_REQUIRED
+    }
+}
+
+impl Default for TextRules {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// One FNV-1a 64 step; hashes are built incrementally during the pass.
+#[inline(always)]
+fn fnv(h: u64, x: u64) -> u64 {
+    (h ^ x).wrapping_mul(FNV_PRIME)
+}
+
+/// splitmix64 finaliser: spreads FNV's weak high bits before SimHash voting.
+#[inline(always)]
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Fixed-size open-addressing set of hashes (no heap). Slots keep the high
+/// 32 bits; the low bits pick the slot, so ~40 bits are compared in all.
+struct SeenSet<const N: usize> {
+    slots: [u32; N],
+    len: usize,
+}
+
+impl<const N: usize> SeenSet<N> {
+    fn new() -> Self {
+        SeenSet { slots: [0; N], len: 0 }
+    }
+
+    /// Add `h`; true if it was already present. Sized so it never fills.
+    fn insert(&mut self, h: u64) -> bool {
+        let fp = (h >> 32) as u32 | 1; // 0 marks an empty slot
+        let mut idx = (h as usize) % N;
+        while self.len < N {
+            if self.slots[idx] == 0 {
+                self.slots[idx] = fp;
+                self.len += 1;
+                return false;
+            }
+            if self.slots[idx] == fp {
+                return true;
+            }
+            idx = (idx + 1) % N;
+        }
+        false
+    }
+}
+
+// ASCII byte classes, one table lookup per byte in the main loop instead of a
+// chain of range compares. Bytes ≥ 0x80 have class 0.
+const C_ALPHA: u8 = 1 << 0;
+const C_VOWEL: u8 = 1 << 1;   // a e i o u, either case
+const C_UPPER: u8 = 1 << 2;
+const C_SPACE: u8 = 1 << 3;   // ' ' only
+const C_WS: u8 = 1 << 4;      // space, \n, \t, \r
+const C_END: u8 = 1 << 5;     // . ! ?
+const C_DIGIT: u8 = 1 << 6;
+
+const BYTE_CLASS: [u8; 256] = byte_classes();
+
+const fn byte_classes() -> [u8; 256] {
+    let mut t = [0u8; 256];
+    let mut b = 0;
+    while b < 128 {
+        let c = b as u8;
+        let lower = c.to_ascii_lowercase();
+        let mut class = 0;
+        if c.is_ascii_alphabetic() { class |= C_ALPHA; }
+        if matches!(lower, b'a' | b'e' | b'i' | b'o' | b'u') { class |= C_VOWEL; }
+        if c.is_ascii_uppercase() { class |= C_UPPER; }
+        if c == b' ' { class |= C_SPACE; }
+        if matches!(c, b' ' | b'\n' | b'\t' | b'\r') { class |= C_WS; }
+        if matches!(c, b'.' | b'!' | b'?') { class |= C_END; }
+        if c.is_ascii_digit() { class |= C_DIGIT; }
+        t[b] = class;
+        b += 1;
+    }
+    t
+}
+
+#[inline(always)]
+fn is_alpha(b: u8) -> bool {
+    BYTE_CLASS[b as usize] & C_ALPHA != 0
+}
+
+#[inline(always)]
+fn to_lower(b: u8) -> u8 {
+    if BYTE_CLASS[b as usize] & C_UPPER != 0 { b | 0x20 } else { b }
+}
+
+/// Suffixes `TextRules::inflect_required_words` accepts after a whole stem.
+const SUFFIXES: [&[u8]; 6] = [b"s", b"es", b"ed", b"ing", b"er", b"ly"];
+/// Suffixes that may follow a doubled final consonant (stop → stopped).
+const DOUBLING_SUFFIXES: [&[u8]; 3] = [b"ed", b"ing", b"er"];
+/// Longest word remainder an inflection can add: doubled consonant + "ing".
+const MAX_SUFFIX_LEN: usize = 4;
+
+/// Lowercased ASCII letters from `pos` to the end of the word, if there are
+/// at most MAX_SUFFIX_LEN of them.
+fn word_rest(pack: &LanguagePack, text: &[u8], pos: usize) -> Option<([u8; MAX_SUFFIX_LEN], usize)> {
+    let mut rest = [0u8; MAX_SUFFIX_LEN];
+    let mut n = 0;
+    while letter_at(pack, text, pos + n) {
+        let b = text[pos + n];
+        if n == MAX_SUFFIX_LEN || !is_alpha(b) {
+            return None;
+        }
+        rest[n] = to_lower(b);
+        n += 1;
+    }
+    Some((rest, n))
+}
+
+/// End of the word if `text[pos..]` finishes it as `stem` + suffix, where
+/// `stem` matched just before `pos`: -s -es -ed -ing -er -ly, -d/-r after a
+/// final e, or a doubled final consonant + -ed/-ing/-er.
+fn inflected_end(pack: &LanguagePack, text: &[u8], pos: usize, stem: &[u8]) -> Option<usize> {
+    let (rest, n) = word_rest(pack, text, pos)?;
+    let rest = &rest[..n];
+    let last = to_lower(stem[stem.len() - 1]);
+    let ok = SUFFIXES.contains(&rest)
+        || (last == b'e' && (rest == b"d" || rest == b"r"))
+        || (n > 1
+            && rest[0] == last
+            && is_alpha(last)
+            && BYTE_CLASS[last as usize] & C_VOWEL == 0
+            && last != b'y'
+            && DOUBLING_SUFFIXES.contains(&&rest[1..]));
+    ok.then_some(pos + n)
+}
+
+/// End of the word if it is `stem` with its final letter changed at `pos`:
+/// e dropped before -ing (dance → dancing), or y → i before -ed -es -er -ly
+/// (carry → carried, happy → happily).
+fn changed_stem_end(pack: &LanguagePack, text: &[u8], pos: usize, stem: &[u8]) -> Option<usize> {
+    let (rest, n) = word_rest(pack, text, pos)?;
+    let rest = &rest[..n];
+    let ok = match to_lower(stem[stem.len() - 1]) {
+        b'e' => rest == b"ing",
+        b'y' => matches!(rest, b"ied" | b"ies" | b"ier" | b"ily"),
+        _ => false,
+    };
+    ok.then_some(pos + n)
+}
+
+/// Letter (ASCII or the pack's accented letters) starting at `pos`.
+pub(crate) fn letter_at(pack: &LanguagePack, text: &[u8], pos: usize) -> bool {
+    if pos >= text.len() { return false; }
+    let b = text[pos];
+    if b < 0x80 { return is_alpha(b); }
+    pos + 1 < text.len() && lang::decode2(b, text[pos + 1]).is_some_and(|cp| pack.is_letter(cp))
+}
+
+/// Letter (ASCII or the pack's accented letters) ending just before `pos`.
+pub(crate) fn letter_before(pack: &LanguagePack, text: &[u8], pos: usize) -> bool {
+    if pos == 0 { return false; }
+    let b = text[pos - 1];
+    if b < 0x80 { return is_alpha(b); }
+    pos >= 2 && lang::decode2(text[pos - 2], b).is_some_and(|cp| pack.is_letter(cp))
+}
+
+/// Why `verify_text` rejected a text — one variant per rule.
+/// Bounds in the comments are the `TextRules::DEFAULT` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextRejection {
+    /// Not 256–800 bytes
+    Length,
+    /// Byte > 127 that isn't valid two-byte UTF-8 for a character the language pack allows
+    NonAscii,
+    /// A sentence has fewer than 5 or more than 35 words
+    SentenceWordCount,
+    /// V2: a sentence doesn't start with a capital letter, or text after the last '.', '!' or '?'
+    SentenceStructure,
+    /// Same sentence appears twice (ignoring case and whitespace)
+    DuplicateSentence,
+    /// Fewer than 2 sentences
+    TooFewSentences,
+    /// No sentence ends in '?'
+    MissingQuestion,
+    /// No sentence of ≤10 words
+    MissingShortSentence,
+    /// No sentence of ≥20 words
+    MissingLongSentence,
+    /// Vowels outside 30–48% of letters
+    VowelRatio,
+    /// Spaces outside 12–22% of bytes
+    SpaceRatio,
+    /// Consonant run >5 or average cluster ≥2.5
+    ConsonantCluster,
+    /// One of the pack's bigrams (English: th/he/in/er/an) seen fewer than 2 times
+    /// (V2: or fewer than len / 80)
+    Bigram,
+    /// Fewer than 28 distinct byte values
+    ByteDiversity,
+    /// Letter frequency chi-squared against the pack model above `max_letter_chi2`
+    LetterFrequency,
+    /// Fewer than `min_trigram_pct` % of letter trigrams are common in the pack's language
+    TrigramFrequency,
+    /// Fewer than `min_dictionary_pct` % of tokens (runs of ASCII letters) are dictionary words
+    DictionaryWords,
+    /// More than `max_repeated_shingle_pct` % of 3-word phrases repeat an earlier one
+    RepeatedPhrases,
+    /// Required word at this index (and all after it) not found in order
+    MissingRequiredWord(usize),
+}
+
+/// Push a finished word; once SHINGLE_WORDS are in, record the shingle ending
+/// here and return whether it was seen before.
+fn shingle_repeats(
+    seen: &mut SeenSet<SHINGLE_SLOTS>,
+    recent: &mut [u64; SHINGLE_WORDS as usize],
+    words_total: &mut u32,
+    word_hash: u64,
+) -> bool {
+    recent.rotate_left(1);
+    recent[SHINGLE_WORDS as usize - 1] = word_hash;
+    *words_total += 1;
+    if *words_total < SHINGLE_WORDS {
+        return false;
+    }
+    let mut h = FNV_OFFSET;
+    for &w in recent.iter() {
+        h = fnv(h, w);
+    }
+    seen.insert(h)
+}
+
+/// 64-bit SimHash of the text's 2-word shingles.
+///
+/// Words are runs of ASCII letters and digits plus any non-ASCII bytes, ASCII
+/// lowercased; punctuation and sentence breaks are ignored. Texts that differ by
+/// a word or two land a few bits apart (see `hamming_distance`), unrelated texts
+/// about 32 apart. Texts under two words hash to 0.
+pub fn simhash(text: &[u8]) -> u64 {
+    let mut votes = [0i16; 64];
+    let mut recent = [0u64; SIMHASH_SHINGLE_WORDS];
+    let mut words = 0usize;
+    let mut word_hash = FNV_OFFSET;
+    let mut word_open = false;
+
+    let mut i = 0;
+    while i <= text.len() {
+        let b = if i < text.len() { text[i] } else { b' ' };
+        if b.is_ascii_alphanumeric() || b >= 0x80 {
+            word_hash = fnv(word_hash, b.to_ascii_lowercase() as u64);
+            word_open = true;
+        } else if word_open {
+            recent.rotate_left(1);
+            recent[SIMHASH_SHINGLE_WORDS - 1] = word_hash;
+            words += 1;
+            if words >= SIMHASH_SHINGLE_WORDS {
+                let mut h = FNV_OFFSET;
+                for &w in recent.iter() {
+                    h = fnv(h, w);
+                }
+                let h = mix64(h);
+                for (bit, vote) in votes.iter_mut().enumerate() {
+                    *vote += if h >> bit & 1 == 1 { 1 } else { -1 };
+                }
+            }
+            word_hash = FNV_OFFSET;
+            word_open = false;
+        }
+        i += 1;
+    }
+
+    let mut fp = 0u64;
+    for (bit, &vote) in votes.iter().enumerate() {
+        if vote > 0 {
+            fp |= 1 << bit;
+        }
+    }
+    fp
+}
+
+/// Number of differing bits between two fingerprints.
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Bits of `fingerprint` in slice `block` of `blocks` near-equal slices.
+///
+/// Two fingerprints within distance `blocks - 1` agree on at least one slice,
+/// which is how the sharded index finds near-duplicates without a full scan.
+pub fn fingerprint_block(fingerprint: u64, block: u8, blocks: u8) -> u64 {
+    let start = block as u32 * 64 / blocks as u32;
+    let end = (block as u32 + 1) * 64 / blocks as u32;
+    let width = end - start;
+    let mask = if width == 64 { u64::MAX } else { (1u64 << width) - 1 };
+    (fingerprint >> start) & mask
+}
+
+/// Shard of the epoch index that `fingerprint` goes in for `block`: that
+/// block's bits modulo `rules.fingerprint_shards`, which must be non-zero.
+pub fn fingerprint_shard(fingerprint: u64, block: u8, rules: &TextRules) -> u8 {
+    let blocks = rules.near_duplicate_bits + 1;
+    (fingerprint_block(fingerprint, block, blocks) % rules.fingerprint_shards as u64) as u8
+}
+
+/// Verify English text meets all natural-language constraints under `rules`.
+pub fn verify_text(text: &[u8], required_words: &[&[u8]], rules: &TextRules) -> Result<(), TextRejection> {
+    verify_text_in(&lang::EN, text, required_words, rules)
+}
+
+/// Verify text in `pack`'s language meets all natural-language constraints under `rules`.
+///
+/// `required_words`: must appear in order, as whole words, at least
+/// `rules.min_word_gap` bytes apart. Returns the first rule that failed.
+///
+/// Not inlined: the dedup tables take ~2.5 KB, which must not share a stack
+/// frame with the caller's (4 KB limit on-chain).
+#[inline(never)]
+pub fn verify_text_in(
+    pack: &LanguagePack,
+    text: &[u8],
+    required_words: &[&[u8]],
+    rules: &TextRules,
+) -> Result<(), TextRejection> {
+    let len = text.len();
+    let v2 = rules.version >= RULES_V2;
+
+    // ── 1. Length ──
+    if len < rules.min_len as usize || len > rules.max_len as usize {
+        return Err(TextRejection::Length);
+    }
+
+    // ── State variables ──
+    let mut letter_count: u32 = 0;
+    let mut vowel_count: u32 = 0;
+    let mut space_count: u32 = 0;
+
+    // Byte diversity: 256-bit bitmap in 4 × u64
+    let mut bmap: [u64; 4] = [0; 4];
+
+    // Bigrams (case-insensitive), counted per `pack.bigrams` entry
+    let mut prev_lower: u8 = 0;
+    let mut bg_counts: [u32; 5] = [0; 5];
+
+    // Lowered continuation byte of the two-byte sequence whose lead was just seen
+    let mut cont_lower: u8 = 0;
+
+    // Frequency model: a–z counts and trigrams over the letter stream
+    let model = pack.model.filter(|_| rules.max_letter_chi2 > 0 || rules.min_trigram_pct > 0);
+    let mut letter_freq: [u32; 26] = [0; 26];
+    let mut tri: [u8; 3] = [0; 3];   // last three lowercase ASCII letters
+    let mut tri_seen: u32 = 0;       // letters pushed into `tri`
+    let mut tri_hits: u32 = 0;
+
+    // Dictionary: tokens are runs of ASCII letters, lowercased
+    let dict = pack.dictionary.filter(|_| rules.min_dictionary_pct > 0);
+    let mut token: [u8; MAX_TOKEN_LEN] = [0; MAX_TOKEN_LEN];
+    let mut token_len: usize = 0;      // may exceed MAX_TOKEN_LEN; then it's not a word
+    let mut token_count: u32 = 0;
+    let mut word_hits: u32 = 0;
+
+    // Consonant clusters
+    let mut cons_run: u32 = 0;
+    let mut cons_max: u32 = 0;
+    let mut cons_total: u32 = 0;
+    let mut cons_count: u32 = 0;
+
+    // Sentence tracking
+    let mut words_in_sent: u32 = 0;
+    let mut in_word: bool = false;
+    let mut sent_count: u32 = 0;
+    let mut question_count: u32 = 0;
+    let mut has_short: bool = false;   // ≤ short_sentence_words
+    let mut has_long: bool = false;    // ≥ long_sentence_words
+    let mut sent_started: bool = false;
+
+    // Sentence dedup over a normalised form: lowercase, whitespace runs → one space
+    let mut sent_seen: SeenSet<DEDUP_SLOTS> = SeenSet::new();
+    let mut sent_hash: u64 = FNV_OFFSET;
+    let mut sent_gap: bool = false;    // whitespace pending inside the sentence
+
+    // Repeated phrases: shingles of SHINGLE_WORDS consecutive words (letters and
+    // digits, lowercased; punctuation and sentence breaks ignored)
+    let shingles_on = rules.max_repeated_shingle_pct > 0;
+    let mut shingle_seen: SeenSet<SHINGLE_SLOTS> = SeenSet::new();
+    let mut word_hash: u64 = FNV_OFFSET;
+    let mut word_open: bool = false;
+    let mut recent_words: [u64; SHINGLE_WORDS as usize] = [0; SHINGLE_WORDS as usize];
+    let mut words_total: u32 = 0;
+    let mut shingles_repeated: u32 = 0;
+
+    // Required word matching
+    let inflect = rules.inflect_required_words && pack.inflections;
+    let rw_total = required_words.len();
+    let mut rw_idx: usize = 0;       // which required word we're looking for
+    let mut rw_match: usize = 0;     // bytes matched so far in current word
+    let mut rw_match_start: usize = 0; // where current match started
+    let mut last_rw_end: usize = 0;  // end position of last matched word
+    let mut has_rw_match: bool = false;
+
+    // ── Main loop ──
+    let mut i: usize = 0;
+    while i < len {
+        let b = text[i];
+        let class = BYTE_CLASS[b as usize];
+        let mut upper = class & C_UPPER != 0;
+        let mut lower = if upper { b | 0x20 } else { b };
+        let mut alpha = class & C_ALPHA != 0;
+        let mut vowel = class & C_VOWEL != 0;
+        let mut punct = false;     // pack punctuation (¿ « …): not a word, doesn't start a sentence
+        let mut cont = false;      // continuation byte: character already counted at its lead
+        let space = class & C_SPACE != 0;
+        let ws = class & C_WS != 0;
+        let sent_end = class & C_END != 0;
+        let digit = class & C_DIGIT != 0;
+
+        // Non-ASCII: only two-byte UTF-8 for characters the pack allows
+        if b > 127 {
+            if cont_lower != 0 {
+                lower = cont_lower;
+                cont_lower = 0;
+                cont = true;
+            } else {
+                let cp = if i + 1 < len { lang::decode2(b, text[i + 1]) } else { None };
+                let cp = cp.ok_or(TextRejection::NonAscii)?;
+                match pack.classify(cp) {
+                    CharClass::Vowel => { alpha = true; vowel = true; }
+                    CharClass::Consonant => alpha = true,
+                    CharClass::Punctuation => punct = true,
+                    CharClass::Disallowed => return Err(TextRejection::NonAscii),
+                }
+                upper = lang::is_upper_cp(cp);
+                cont_lower = 0x80 | (lang::lower_cp(cp) & 0x3F) as u8;
+            }
+        }
+
+        // Byte diversity
+        bmap[(b >> 6) as usize] |= 1u64 << (b & 63);
+
+        // Letter / vowel / space counts and consonant clusters (once per character)
+        if !cont {
+            if alpha {
+                letter_count += 1;
+                if vowel { vowel_count += 1; }
+            }
+            if space { space_count += 1; }
+
+            if let Some(m) = model {
+                if lower.is_ascii_lowercase() {
+                    letter_freq[(lower - b'a') as usize] += 1;
+                    tri = [tri[1], tri[2], lower];
+                    tri_seen += 1;
+                    if tri_seen >= 3 && m.trigrams.binary_search(&tri).is_ok() {
+                        tri_hits += 1;
+                    }
+                }
+            }
+
+            if alpha && !vowel {
+                cons_run += 1;
+            } else if cons_run > 0 {
+                if cons_run > cons_max { cons_max = cons_run; }
+                cons_total += cons_run;
+                cons_count += 1;
+                cons_run = 0;
+            }
+        }
+
+        // Dictionary tokens
+        if let Some(bits) = dict {
+            if lower.is_ascii_lowercase() {
+                if token_len < MAX_TOKEN_LEN { token[token_len] = lower; }
+                token_len += 1;
+            } else if token_len > 0 {
+                token_count += 1;
+                if token_len <= MAX_TOKEN_LEN && dictionary::contains_inflected(bits, &token[..token_len]) {
+                    word_hits += 1;
+                }
+                token_len = 0;
+            }
+        }
+
+        // Bigram detection (ASCII letter pairs; accented bytes never match)
+        if alpha && !cont && prev_lower.is_ascii_lowercase() {
+            let mut k = 0;
+            while k < pack.bigrams.len() {
+                if pack.bigrams[k][0] == prev_lower && pack.bigrams[k][1] == lower {
+                    bg_counts[k] += 1;
+                }
+                k += 1;
+            }
+        }
+        prev_lower = lower;
+
+        // Word tracking within sentence
+        if ws || sent_end || punct {
+            in_word = false;
+        } else if !in_word && !cont {
+            in_word = true;
+            words_in_sent += 1;
+        }
+
+        // Sentence start (skip leading whitespace and opening punctuation)
+        if !sent_started && !ws && !sent_end && !punct && !cont {
+            if v2 && !upper {
+                return Err(TextRejection::SentenceStructure);
+            }
+            sent_started = true;
+        }
+
+        // Normalised sentence hash
+        if sent_started {
+            if ws {
+                sent_gap = true;
+            } else {
+                if sent_gap {
+                    sent_hash = fnv(sent_hash, b' ' as u64);
+                    sent_gap = false;
+                }
+                sent_hash = fnv(sent_hash, lower as u64);
+            }
+        }
+
+        // Shingles: close a word on any non-word byte
+        if shingles_on {
+            if alpha || cont || digit {
+                word_hash = fnv(word_hash, lower as u64);
+                word_open = true;
+            } else if word_open {
+                if shingle_repeats(&mut shingle_seen, &mut recent_words, &mut words_total, word_hash) {
+                    shingles_repeated += 1;
+                }
+                word_hash = FNV_OFFSET;
+                word_open = false;
+            }
+        }
+
+        // ── Required word matching (with word boundary check) ──
+        if rw_idx < rw_total {
+            let rw = required_words[rw_idx];
+            let mut done = false;                  // attempt over: record `found_end` if any, then reset
+            let mut found_end: Option<usize> = None; // end of a whole-word occurrence of `rw`
+            if !rw.is_empty() && lower == to_lower(rw[rw_match]) {
+                if rw_match == 0 {
+                    rw_match_start = i;
+                }
+                rw_match += 1;
+                if rw_match == rw.len() {
+                    done = true;
+                    found_end = if !letter_at(pack, text, i + 1) {
+                        Some(i + 1)
+                    } else if inflect {
+                        inflected_end(pack, text, i + 1, rw)
+                    } else {
+                        None
+                    };
+                }
+            } else if rw_match > 0 {
+                // Match interrupted; with inflections, maybe at a changed final e / y
+                done = true;
+                if inflect && rw_match + 1 == rw.len() {
+                    found_end = changed_stem_end(pack, text, i, rw);
+                }
+            }
+
+            if done {
+                if let Some(end) = found_end {
+                    // Check the boundary before and the minimum gap from the previous match;
+                    // otherwise skip this occurrence
+                    let gap_ok = !has_rw_match || rw_match_start >= last_rw_end + rules.min_word_gap as usize;
+                    if !letter_before(pack, text, rw_match_start) && gap_ok {
+                        last_rw_end = end;
+                        has_rw_match = true;
+                        rw_idx += 1;
+                    }
+                }
+                // Reset and check if current byte starts new match
+                // (handles both: failure → retry same word,
+                //  and success → check next word)
+                rw_match = 0;
+                if rw_idx < rw_total {
+                    let rw_next = required_words[rw_idx];
+                    if !rw_next.is_empty() && lower == to_lower(rw_next[0]) {
+                        rw_match_start = i;
+                        rw_match = 1;
+                    }
+                }
+            }
+        }
+
+        // ── Sentence end ──
+        if sent_end && words_in_sent > 0 && sent_started {
+            // Word count bounds
+            if words_in_sent < rules.min_sentence_words as u32 || words_in_sent > rules.max_sentence_words as u32 {
+                return Err(TextRejection::SentenceWordCount);
+            }
+            if b == b'?' { question_count += 1; }
+            if words_in_sent <= rules.short_sentence_words as u32 { has_short = true; }
+            if words_in_sent >= rules.long_sentence_words as u32 { has_long = true; }
+
+            // Sentence dedup
+            if sent_seen.insert(sent_hash) {
+                return Err(TextRejection::DuplicateSentence);
+            }
+            sent_count += 1;
+
+            // Reset sentence state
+            words_in_sent = 0;
+            in_word = false;
+            sent_started = false;
+            sent_hash = FNV_OFFSET;
+            sent_gap = false;
+        }
+
+        i += 1;
+    }
+
+    // Flush trailing shingle word
+    if word_open && shingle_repeats(&mut shingle_seen, &mut recent_words, &mut words_total, word_hash) {
+        shingles_repeated += 1;
+    }
+
+    // Flush trailing token
+    if let Some(bits) = dict {
+        if token_len > 0 {
+            token_count += 1;
+            if token_len <= MAX_TOKEN_LEN && dictionary::contains_inflected(bits, &token[..token_len]) {
+                word_hits += 1;
+            }
+        }
+    }
+
+    // Flush trailing consonant cluster
+    if cons_run > 0 {
+        if cons_run > cons_max { cons_max = cons_run; }
+        cons_total += cons_run;
+        cons_count += 1;
+    }
+
+    // ── Post-loop checks ──
+
+    // V2: no unterminated trailing sentence
+    if v2 && sent_started { return Err(TextRejection::SentenceStructure); }
+
+    // All required words found
+    if rw_idx < rw_total { return Err(TextRejection::MissingRequiredWord(rw_idx)); }
+
+    // Sentence structure
+    if sent_count < rules.min_sentences as u32 { return Err(TextRejection::TooFewSentences); }
+    if question_count < rules.min_questions as u32 { return Err(TextRejection::MissingQuestion); }
+    if !has_short { return Err(TextRejection::MissingShortSentence); }
+    if !has_long { return Err(TextRejection::MissingLongSentence); }
+
+    // Vowel ratio (% of letters)
+    if letter_count == 0 { return Err(TextRejection::VowelRatio); }
+    let vc = vowel_count as u64;
+    let lc = letter_count as u64;
+    if vc * 100 < rules.min_vowel_pct as u64 * lc || vc * 100 > rules.max_vowel_pct as u64 * lc {
+        return Err(TextRejection::VowelRatio);
+    }
+
+    // Space ratio (% of total bytes)
+    let sc = space_count as u64;
+    let total = len as u64;
+    if sc * 100 < rules.min_space_pct as u64 * total || sc * 100 > rules.max_space_pct as u64 * total {
+        return Err(TextRejection::SpaceRatio);
+    }
+
+    // Consonant clusters: longest run and average
+    if cons_max > rules.max_consonant_run as u32 { return Err(TextRejection::ConsonantCluster); }
+    if cons_count > 0 && cons_total * 10 >= rules.max_avg_cluster_x10 as u32 * cons_count {
+        return Err(TextRejection::ConsonantCluster);
+    }
+
+    // Bigrams: each of the pack's five ≥ min_bigram_count (V2: and ≥ len / 80)
+    let mut bg_min = rules.min_bigram_count as u32;
+    if v2 && (len / 80) as u32 > bg_min { bg_min = (len / 80) as u32; }
+    if bg_counts.iter().any(|&c| c < bg_min) {
+        return Err(TextRejection::Bigram);
+    }
+
+    // Byte diversity
+    // (natural English text has ~31-34: 22-25 lowercase + 3-5 uppercase + 4-6 punctuation)
+    let unique = bmap[0].count_ones() + bmap[1].count_ones()
+               + bmap[2].count_ones() + bmap[3].count_ones();
+    if unique < rules.min_unique_bytes as u32 { return Err(TextRejection::ByteDiversity); }
+
+    // Frequency model: Σ (observed − expected)² / expected over a–z, in fixed
+    // point (×10 000). Expected shares are floored at 0.1% so one rare letter
+    // can't dominate. Costs 26 divisions; the loop adds a ≤6-step binary search
+    // per letter.
+    if let Some(m) = model {
+        if rules.max_letter_chi2 > 0 {
+            let n = letter_freq.iter().sum::<u32>() as u64;
+            let mut chi2_x10k: u64 = 0;
+            let mut k = 0;
+            while k < 26 {
+                let expected = n * m.letters[k].max(10) as u64; // × 10 000
+                if expected > 0 {
+                    let observed = letter_freq[k] as u64 * 10_000;
+                    let diff = observed.abs_diff(expected);
+                    chi2_x10k += diff * diff / expected;
+                }
+                k += 1;
+            }
+            if chi2_x10k > rules.max_letter_chi2 as u64 * 10_000 {
+                return Err(TextRejection::LetterFrequency);
+            }
+        }
+        if rules.min_trigram_pct > 0 {
+            let trigrams = tri_seen.saturating_sub(2);
+            if tri_hits * 100 < rules.min_trigram_pct as u32 * trigrams {
+                return Err(TextRejection::TrigramFrequency);
+            }
+        }
+    }
+
+    // Dictionary word ratio
+    if dict.is_some() && word_hits * 100 < rules.min_dictionary_pct as u32 * token_count {
+        return Err(TextRejection::DictionaryWords);
+    }
+
+    // Repeated phrases
+    if shingles_on {
+        let shingles = words_total.saturating_sub(SHINGLE_WORDS - 1);
+        if shingles_repeated * 100 > rules.max_repeated_shingle_pct as u32 * shingles {
+            return Err(TextRejection::RepeatedPhrases);
+        }
+    }
+
+    Ok(())
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn natural_text() -> Vec<u8> {
+        let text = "The weather in the morning was rather interesting and \
+            pleasant for an early spring day in the northern hemisphere. \
+            Have you ever wondered whether the inner workings of nature \
+            can truly be understood through simple observation and careful \
+            thinking about the patterns that emerge in everything around us? \
+            The ancient trees in the garden were standing tall and their \
+            branches reached toward the bright sky above. \
+            The morning air felt crisp and fresh. \
+            Another interesting thing happened when the river began to \
+            change direction and the water flowed in an entirely different \
+            manner than before. \
+            Is there anything more beautiful than a quiet evening spent \
+            reading by the fireplace?";
+        text.as_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_natural_passes() {
+        let text = natural_text();
+        let words: &[&[u8]] = &[b"weather", b"nature", b"ancient"];
+        assert_eq!(verify_text(&text, words, &TextRules::DEFAULT), Ok(()), "Natural text should pass, len={}", text.len());
+    }
+
+    #[test]
+    fn test_too_short() {
+        assert_eq!(verify_text(b"Hello world.", &[], &TextRules::DEFAULT), Err(TextRejection::Length));
+    }
+
+    #[test]
+    fn test_duplicate_sentences() {
+        let s1 = "The weather in the morning was rather interesting and pleasant. ";
+        let q = "Have you ever wondered whether the inner workings of nature can truly be understood? ";
+        let long = "The ancient trees in the garden were standing tall and their branches reached toward the bright sky creating an interesting pattern. ";
+        let mut t = String::new();
+        t.push_str(s1); t.push_str(q); t.push_str(long); t.push_str(s1); // dup!
+        while t.len() < 256 { t.push_str("Another filler sentence in the text here today. "); }
+        assert_eq!(verify_text(t.as_bytes(), &[], &TextRules::DEFAULT), Err(TextRejection::DuplicateSentence), "Duplicate sentences should fail");
+
+        // Case and whitespace don't make a sentence new
+        let mut t = String::new();
+        t.push_str(s1); t.push_str(q); t.push_str(long);
+        t.push_str("The  weather in the MORNING was rather\tinteresting and pleasant. ");
+        while t.len() < 256 { t.push_str("Another filler sentence in the text here today. "); }
+        assert_eq!(verify_text(t.as_bytes(), &[], &TextRules::DEFAULT), Err(TextRejection::DuplicateSentence));
+    }
+
+    #[test]
+    fn test_no_question() {
+        let t = "The weather in the morning was rather interesting and pleasant for an early spring day. \
+            The ancient trees in the garden were standing tall and their branches reached toward the bright sky above and beyond the hills. \
+            Another interesting thing happened when the river began to change direction and the water flowed in an entirely different manner than before. \
+            The evening settled over the land.";
+        let padded = format!("{} {}", t, "More filler text about the interesting weather and the ancient garden path. \
+            Other filler text about the pleasant weather and the winding river bank. ");
+        assert_eq!(verify_text(padded.as_bytes(), &[], &TextRules::DEFAULT), Err(TextRejection::MissingQuestion), "Missing question should fail");
+    }
+
+    #[test]
+    fn test_missing_required_word() {
+        let text = natural_text();
+        let words: &[&[u8]] = &[b"weather", b"blockchain", b"ancient"];
+        assert_eq!(verify_text(&text, words, &TextRules::DEFAULT), Err(TextRejection::MissingRequiredWord(1)), "Missing required word should fail");
+    }
+
+    #[test]
+    fn test_wrong_word_order() {
+        let text = natural_text();
+        let words: &[&[u8]] = &[b"ancient", b"nature"];
+        assert_eq!(verify_text(&text, words, &TextRules::DEFAULT), Err(TextRejection::MissingRequiredWord(1)), "Wrong word order should fail");
+    }
+
+    #[test]
+    fn test_word_boundary() {
+        // "other" contains "the" but should NOT match required word "the"
+        // when checking word boundaries
+        let t = "Another other thing happened in the morning when the weather changed and everything \
+            looked rather different from what the ancient stories had described in their pages. \
+            Is there anything more interesting than discovering the hidden patterns in nature? \
+            The answer to this question remains unclear even today. \
+            Sometimes the world reveals its secrets only to those who listen carefully and patiently.";
+        let padded = format!("{} {}", t, "The garden path led through the forest and over the bridge. ");
+        // "other" appears before standalone "the" — should still find standalone "the"
+        let words: &[&[u8]] = &[b"the"];
+        // This should pass because standalone "the" exists
+        if padded.len() >= 256 {
+            assert_eq!(verify_text(padded.as_bytes(), words, &TextRules::DEFAULT), Ok(()), "Word boundary: standalone 'the' should match");
+        }
+    }
+
+    #[test]
+    fn test_gibberish() {
+        let mut g = Vec::with_capacity(300);
+        let cons = b"bcdfghjklmnpqrstvwxyz";
+        for i in 0u16..300 {
+            if i % 7 == 0 { g.push(b' '); }
+            else if i % 50 == 49 { g.push(b'.'); }
+            else { g.push(cons[(i as usize) % cons.len()]); }
+        }
+        assert!(verify_text(&g, &[], &TextRules::DEFAULT).is_err(), "Gibberish should fail");
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let text = natural_text();
+        let words: &[&[u8]] = &[b"weather", b"nature", b"ancient"];
+        assert!(TextRules::DEFAULT.is_valid());
+
+        let longer = TextRules { min_len: text.len() as u16 + 1, ..TextRules::DEFAULT };
+        assert_eq!(verify_text(&text, words, &longer), Err(TextRejection::Length));
+
+        let more_questions = TextRules { min_questions: 3, ..TextRules::DEFAULT };
+        assert_eq!(verify_text(&text, words, &more_questions), Err(TextRejection::MissingQuestion));
+
+        let wide_gap = TextRules { min_word_gap: 400, ..TextRules::DEFAULT };
+        assert_eq!(verify_text(&text, words, &wide_gap), Err(TextRejection::MissingRequiredWord(1)));
+
+        let inverted = TextRules { min_vowel_pct: 60, ..TextRules::DEFAULT };
+        assert!(!inverted.is_valid());
+    }
+
+    #[test]
+    fn test_difficulty_scaling() {
+        // Off by default: identical at every difficulty
+        assert_eq!(TextRules::DEFAULT.for_difficulty(250), TextRules::DEFAULT);
+
+        let base = TextRules { scale_with_difficulty: true, ..TextRules::DEFAULT };
+        assert_eq!(base.for_difficulty(8), base);
+
+        let hard = base.for_difficulty(41);
+        assert_eq!(hard.min_len, 416);
+        assert_eq!(hard.min_sentences, 4);
+        assert_eq!(hard.min_questions, 2);
+        assert_eq!(hard.min_word_gap, 80);
+        assert_eq!(hard.min_bigram_count, 4);
+        assert!(hard.is_valid());
+
+        // Monotone: a higher difficulty is never easier
+        let mut prev = base.for_difficulty(0);
+        for d in [11u64, 16, 21, 31, 41, 250] {
+            let r = base.for_difficulty(d);
+            assert!(r.min_len >= prev.min_len && r.min_word_gap >= prev.min_word_gap);
+            assert!(r.min_sentences >= prev.min_sentences && r.min_bigram_count >= prev.min_bigram_count);
+            prev = r;
+        }
+
+        // One question is enough at tier 0 but not at tier 5
+        let mut text = natural_text();
+        let cut = text.windows(3).position(|w| w == b" Is").unwrap();
+        text.truncate(cut);
+        let words: &[&[u8]] = &[b"weather", b"nature", b"ancient"];
+        assert_eq!(verify_text(&text, words, &base.for_difficulty(8)), Ok(()));
+        assert_eq!(verify_text(&text, words, &hard), Err(TextRejection::MissingQuestion));
+    }
+
+    /// Shared vectors pinning the published spec (repo-root `tests/vectors/text_rules.json`).
+    #[test]
+    fn test_vectors() {
+        let doc: serde_json::Value =
+            serde_json::from_str(include_str!("../../../tests/vectors/text_rules.json")).unwrap();
+
+        let preset = |name: &str| match name {
+            "default" => TextRules::DEFAULT,
+            "published" => TextRules::PUBLISHED,
+            other => panic!("unknown rule set {other}"),
+        };
+        for (name, fields) in doc["rule_sets"].as_object().unwrap() {
+            let rules = preset(name);
+            let expected = serde_json::json!({
+                "min_len": rules.min_len, "max_len": rules.max_len,
+                "min_sentence_words": rules.min_sentence_words, "max_sentence_words": rules.max_sentence_words,
+                "short_sentence_words": rules.short_sentence_words, "long_sentence_words": rules.long_sentence_words,
+                "min_sentences": rules.min_sentences, "min_questions": rules.min_questions,
+                "min_vowel_pct": rules.min_vowel_pct, "max_vowel_pct": rules.max_vowel_pct,
+                "min_space_pct": rules.min_space_pct, "max_space_pct": rules.max_space_pct,
+                "max_consonant_run": rules.max_consonant_run, "max_avg_cluster_x10": rules.max_avg_cluster_x10,
+                "min_bigram_count": rules.min_bigram_count, "min_unique_bytes": rules.min_unique_bytes,
+                "min_word_gap": rules.min_word_gap, "scale_with_difficulty": rules.scale_with_difficulty,
+                "version": rules.version, "languages": rules.languages,
+                "max_letter_chi2": rules.max_letter_chi2, "min_trigram_pct": rules.min_trigram_pct,
+                "min_dictionary_pct": rules.min_dictionary_pct,
+                "max_repeated_shingle_pct": rules.max_repeated_shingle_pct,
+                "fingerprint_shards": rules.fingerprint_shards,
+                "near_duplicate_bits": rules.near_duplicate_bits,
+                "inflect_required_words": rules.inflect_required_words,
+                "challenge_kinds": rules.challenge_kinds,
+                "word_derivation": rules.word_derivation, "extra_words": rules.extra_words,
+            });
+            assert_eq!(fields, &expected, "rule set {name} drifted from the spec");
+        }
+
+        for v in doc["vectors"].as_array().unwrap() {
+            let name = v["name"].as_str().unwrap();
+            let words: Vec<&[u8]> = v["words"].as_array().unwrap()
+                .iter().map(|w| w.as_str().unwrap().as_bytes()).collect();
+            let got = match verify_text(v["text"].as_str().unwrap().as_bytes(), &words, &preset(v["rules"].as_str().unwrap())) {
+                Ok(()) => "Ok".to_string(),
+                Err(r) => format!("{r:?}"),
+            };
+            assert_eq!(got, v["expect"].as_str().unwrap(), "vector: {name}");
+        }
+    }
+
+    #[test]
+    fn test_inflected_required_words() {
+        let inflect = TextRules { inflect_required_words: true, ..TextRules::DEFAULT };
+        let text = String::from_utf8(natural_text()).unwrap();
+
+        // (replace, with, stem): each form counts only when the flag is set
+        let forms = [
+            ("the river began", "the rivers began", "river"),                     // -s
+            ("ancient trees in", "ancient trees and bushes in", "bush"),          // -es
+            ("ever wondered", "ever wandered", "wander"),                         // -ed
+            ("began to change", "began gathering and", "gather"),                 // -ing
+            ("thinking about", "thinking longer about", "long"),                  // -er
+            ("careful thinking", "slowly thinking", "slow"),                      // -ly
+            ("began to change direction", "kept changing direction", "change"),   // e dropped before -ing
+            ("the river began to change direction", "the river changed direction", "change"), // e + d
+            ("the water flowed", "the water hurried", "hurry"),                   // y → ied
+            ("the water flowed", "the water carries on", "carry"),                // y → ies
+            ("the water flowed", "the water kept carrying", "carry"),             // y + ing
+            ("through simple observation", "through happily simple observation", "happy"), // y → ily
+            ("the river began", "the river stopped and began", "stop"),           // doubled consonant
+        ];
+        for (from, to, stem) in forms {
+            let t = text.replace(from, to);
+            assert_ne!(t, text, "{from}");
+            let words = [stem.as_bytes()];
+            assert_eq!(verify_text(t.as_bytes(), &words, &inflect), Ok(()), "{to}");
+            assert_eq!(verify_text(t.as_bytes(), &words, &TextRules::DEFAULT), Err(TextRejection::MissingRequiredWord(0)), "{to}");
+        }
+
+        // Not inflections: compounds, stacked suffixes, other endings, prefixes
+        let not_forms = [
+            ("the river began", "the riverside began", "river"),
+            ("the water flowed", "the water hurriedly flowed", "hurry"),
+            ("began to change", "began a changable", "change"),
+            ("the river began", "the river unstopped", "stop"),
+        ];
+        for (from, to, stem) in not_forms {
+            let t = text.replace(from, to);
+            assert_eq!(verify_text(t.as_bytes(), &[stem.as_bytes()], &inflect), Err(TextRejection::MissingRequiredWord(0)), "{to}");
+        }
+
+        // The gap rule still applies to inflected forms
+        let close: &[&[u8]] = &[b"wonder", b"working"];
+        assert_eq!(verify_text(text.as_bytes(), close, &inflect), Err(TextRejection::MissingRequiredWord(1)));
+        let apart: &[&[u8]] = &[b"wonder", b"branch"];
+        assert_eq!(verify_text(text.as_bytes(), apart, &inflect), Ok(()));
+    }
+
+    #[test]
+    fn test_cu_corpus() {
+        // The CU harness must never fail on a text: every corpus entry passes its
+        // rule set whatever wordlist words the epoch requires.
+        let doc: serde_json::Value =
+            serde_json::from_str(include_str!("../../../tests/vectors/cu_corpus.json")).unwrap();
+        for entry in doc["texts"].as_array().unwrap() {
+            let name = entry["name"].as_str().unwrap();
+            let rules = match entry["rules"].as_str().unwrap() {
+                "default" => TextRules::DEFAULT,
+                "published" => TextRules::PUBLISHED,
+                other => panic!("unknown rule set {other}"),
+            };
+            for word in words::WORDLIST {
+                let text = entry["text"].as_str().unwrap()
+                    .replace("{0}", word).replace("{1}", word).replace("{2}", word);
+                let required = [word.as_bytes(); 3];
+                assert_eq!(verify_text(text.as_bytes(), &required, &rules), Ok(()), "{name} with {word}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_english_model() {
+        let english = TextRules { max_letter_chi2: 150, min_trigram_pct: 15, ..TextRules::DEFAULT };
+        assert!(english.is_valid());
+        let words: &[&[u8]] = &[b"weather", b"nature", b"ancient"];
+        assert_eq!(verify_text(&natural_text(), words, &english), Ok(()));
+
+        // Each of these passes the V1 statistics but is not English.
+
+        // Bigram stuffing: th/he/in/er/an everywhere, letter mix far from English
+        let stuffed = "There then the hen ran in an inner den and the other hen ran in there. \
+            Then the thin hen ran in and the other one ran in the den then? \
+            The hen in the den then ran in an inner den where the other thin hen ran in and then the hen ran there again and again. \
+            Then the other hen ran. Where then was the other hen in the den? \
+            Either hen ran in the inner den, neither hen ran in the outer den, so then the hen ran back in there quickly. \
+            My big fox Jack saw Pete.";
+        assert_eq!(verify_text(stuffed.as_bytes(), &[], &TextRules::DEFAULT), Ok(()));
+        assert_eq!(verify_text(stuffed.as_bytes(), &[], &english), Err(TextRejection::LetterFrequency));
+
+        // Latin filler with the bigrams spliced in
+        let lorem = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed thenia eiusmod tempor incididunt ut labore et dolore magna aliqua ad minim veniam. \
+            Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat thera? \
+            Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur sanctus. \
+            Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum thena hera. \
+            Nam libero tempore, cum soluta nobis est.";
+        assert_eq!(verify_text(lorem.as_bytes(), &[], &TextRules::DEFAULT), Ok(()));
+        assert_eq!(verify_text(lorem.as_bytes(), &[], &english), Err(TextRejection::LetterFrequency));
+
+        // Pronounceable non-words with an English-like letter mix: only trigrams catch it
+        let pseudo = "Balo mira tenan sorel gaby weda kinthe cofu. \
+            Zavi thenor linda pesho ranen ula moki hertan voli senna rithe palo dunin. \
+            Ferna kolin thesa mundo rivan helo tapi nerin, sovan lumi thero vasin kedar minha? \
+            Oran helis tanvi poru desan thel mivar konae, lisu renan hethi oma pavelin dorun kisa thano merin sola vendi tarin. \
+            Tavi kelon hera sunan moli.";
+        assert_eq!(verify_text(pseudo.as_bytes(), &[], &TextRules::DEFAULT), Ok(()));
+        assert_eq!(verify_text(pseudo.as_bytes(), &[], &english), Err(TextRejection::TrigramFrequency));
+
+        // The same non-words fail the dictionary rule on their own
+        let words_only = TextRules { min_dictionary_pct: 70, ..TextRules::DEFAULT };
+        assert_eq!(verify_text(pseudo.as_bytes(), &[], &words_only), Err(TextRejection::DictionaryWords));
+        assert_eq!(verify_text(&natural_text(), &[], &words_only), Ok(()));
+    }
+
+    #[test]
+    fn test_byte_classes() {
+        for b in 0..=255u8 {
+            let class = BYTE_CLASS[b as usize];
+            assert_eq!(class & C_ALPHA != 0, b.is_ascii_alphabetic(), "{b}");
+            assert_eq!(class & C_UPPER != 0, b.is_ascii_uppercase(), "{b}");
+            assert_eq!(class & C_VOWEL != 0, b"aeiouAEIOU".contains(&b), "{b}");
+            assert_eq!(class & C_SPACE != 0, b == b' ', "{b}");
+            assert_eq!(class & C_WS != 0, b" \n\t\r".contains(&b), "{b}");
+            assert_eq!(class & C_END != 0, b".!?".contains(&b), "{b}");
+            assert_eq!(class & C_DIGIT != 0, b.is_ascii_digit(), "{b}");
+            assert_eq!(to_lower(b), b.to_ascii_lowercase(), "{b}");
+        }
+    }
+
+    #[test]
+    fn test_simhash() {
+        let text = natural_text();
+        let fp = simhash(&text);
+        assert_ne!(fp, 0);
+        let t = String::from_utf8(text).unwrap();
+
+        // Case, whitespace and punctuation don't change the fingerprint
+        let shouted = t.to_uppercase().replace(", ", " ").replace(' ', "  ");
+        assert_eq!(simhash(shouted.as_bytes()), fp);
+
+        // A one-word edit stays close; several stay closer than unrelated text
+        let swapped = t.replacen("ancient", "old", 1);
+        assert!(hamming_distance(simhash(swapped.as_bytes()), fp) <= 5);
+        let edited = swapped.replacen("crisp", "cool", 1).replacen("quiet", "calm", 1);
+        let unrelated = "Every winter the village gathered near the frozen lake to share stories and warm bread. \
+            Did anyone remember when the old mill still turned its heavy wheel beside the water? \
+            Children skated while their parents talked about harvests, weather, and the price of grain at the market in town. \
+            Snow fell quietly. Later that night a bright moon rose over the hills and lit the narrow road home.";
+        let far = hamming_distance(simhash(unrelated.as_bytes()), fp);
+        assert!(hamming_distance(simhash(edited.as_bytes()), fp) < far / 2, "unrelated distance {}", far);
+
+        assert_eq!(simhash(b"one"), 0);
+    }
+
+    #[test]
+    fn test_fingerprint_blocks() {
+        let fp = 0x0123_4567_89ab_cdef;
+        assert_eq!(fingerprint_block(fp, 0, 1), fp);
+        assert_eq!(fingerprint_block(fp, 0, 4), 0xcdef);
+        assert_eq!(fingerprint_block(fp, 3, 4), 0x0123);
+        // Uneven split: 21, 21, 22 bits, covering every bit once
+        let mut joined = 0;
+        for block in 0..3 {
+            joined |= fingerprint_block(fp, block, 3) << (block as u32 * 64 / 3);
+        }
+        assert_eq!(joined, fp);
+
+        // Within distance blocks - 1, some block matches
+        let near = fp ^ 0x8000_0001_0000_0100;
+        assert!((0..4).any(|b| fingerprint_block(fp, b, 4) == fingerprint_block(near, b, 4)));
+
+        let rules = TextRules { fingerprint_shards: 10, near_duplicate_bits: 3, ..TextRules::DEFAULT };
+        assert_eq!(fingerprint_shard(fp, 0, &rules), (0xcdef % 10) as u8);
+        assert_eq!(fingerprint_shard(fp, 3, &rules), (0x0123 % 10) as u8);
+    }
+
+    #[test]
+    fn test_repeated_phrases() {
+        let rules = TextRules { max_repeated_shingle_pct: 10, ..TextRules::DEFAULT };
+        assert!(rules.is_valid());
+        assert_eq!(verify_text(&natural_text(), &[], &rules), Ok(()));
+
+        // Templated filler: every sentence is new, but the phrases are not
+        let filler = "The weather in the garden was rather pleasant for an early spring day in the north. \
+            Have you ever wondered why the weather in the garden was rather pleasant for an early spring day? \
+            The weather in the valley was rather pleasant for an early autumn day in the south of the country. \
+            The river was very calm. \
+            The weather in the city was rather pleasant for an early summer day in the west, my friend Jack said. \
+            Quite a pleasant day indeed.";
+        assert_eq!(verify_text(filler.as_bytes(), &[], &TextRules::DEFAULT), Ok(()));
+        assert_eq!(verify_text(filler.as_bytes(), &[], &rules), Err(TextRejection::RepeatedPhrases));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let mut text = natural_text();
+        let pos = text.iter().position(|&b| b == b'w').unwrap();
+        text[pos] = 0xC3;
+        assert_eq!(verify_text(&text, &[], &TextRules::DEFAULT), Err(TextRejection::NonAscii));
+    }
+}
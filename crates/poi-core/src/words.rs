@@ -0,0 +1,837 @@
+//! Word list and derivation for Proof of Inference.
+//!
+//! Common English words (4-8 letters), used to derive required words from
+//! the challenge seed deterministically. `MineState::wordlist_version` picks
+//! the built-in list: the original 200 words (V1) or 2048 (V2); governance can
+//! also activate an on-chain `WordList` account, read as a `PackedWordlist`.
+//! Wordlists are stored grouped by part of speech (nouns, verbs, adjectives,
+//! adverbs) so a challenge can ask for a grammatical pattern.
+
+use crate::keccak;
+use core::ops::Range;
+
+pub const WORDLIST_SIZE: usize = 200;
+pub const MAX_REQUIRED: usize = 16;
+pub const MAX_WORD_LEN: usize = 8;
+/// Shortest word a wordlist may hold, in characters.
+pub const MIN_WORD_CHARS: usize = 4;
+
+/// English wordlist revisions (`MineState::wordlist_version`). A stored 0 means V1.
+/// V1: `WORDLIST`, 200 words.
+pub const WORDLIST_V1: u8 = 1;
+/// V2: `WORDLIST_2048`.
+pub const WORDLIST_V2: u8 = 2;
+/// Newest revision governance may select.
+pub const LATEST_WORDLIST: u8 = WORDLIST_V2;
+
+/// Derivation revisions (`verify::TextRules::word_derivation`). A stored 0 means V1.
+/// V1: word i from seed bytes 2i, 2i+1 modulo the list size, linear probing
+/// past duplicates. Slightly biased, and the seed only covers this many words.
+pub const DERIVATION_V1: u8 = 1;
+pub const V1_MAX_REQUIRED: usize = 8;
+/// V2: uniform draws from a keccak stream over the seed (see `WordStream`),
+/// redrawing duplicates; any list size, up to MAX_REQUIRED words.
+pub const DERIVATION_V2: u8 = 2;
+/// Newest revision `TextRules::is_valid` accepts.
+pub const LATEST_DERIVATION: u8 = DERIVATION_V2;
+
+/// Domain tag of the V2 stream.
+const STREAM_TAG: &[u8] = b"poi-words";
+
+pub const WORDLIST: [&str; WORDLIST_SIZE] = [
+    // Nouns (70)
+    "time","life","world","place","water","light","house","music","power","dream",
+    "heart","earth","ocean","river","cloud","stone","flame","voice","night","field",
+    "space","brain","truth","peace","storm","tower","plant","metal","glass","wheel",
+    "bridge","forest","garden","market","island","desert","silver","shadow","spirit","nature",
+    "energy","future","memory","moment","season","winter","summer","signal","system","design",
+    "method","reason","answer","letter","person","animal","flower","morning","evening","journey",
+    "history","culture","balance","freedom","pattern","shelter","surface","chapter","element","silence",
+    // Verbs (50)
+    "think","learn","build","write","speak","dance","climb","watch","shine","carry",
+    "drive","paint","teach","reach","solve","share","trust","guide","shape","craft",
+    "chase","drift","weave","bloom","grasp","shift","sweep","trace","wander","gather",
+    "create","follow","listen","notice","wonder","happen","become","remain","travel","return",
+    "search","reveal","explore","imagine","connect","protect","reflect","develop","consider","discover",
+    // Adjectives (50)
+    "bright","quiet","gentle","strong","simple","hidden","golden","silent","frozen","bitter",
+    "tender","vivid","subtle","fierce","humble","steady","clever","honest","broken","sacred",
+    "unique","global","active","native","smooth","narrow","liquid","mental","social","visual",
+    "formal","casual","proper","remote","secure","stable","cosmic","ancient","modern","natural",
+    "digital","central","special","private","perfect","strange","careful","curious","distant","endless",
+    // Adverbs (30)
+    "often","never","always","slowly","deeply","gently","simply","nearly","barely","mostly",
+    "partly","surely","truly","fully","quite","still","maybe","hence","twice","ahead",
+    "apart","aside","along","after","again","early","later","since","almost","around",
+];
+
+/// `WORDLIST` with its part-of-speech groups.
+pub const ENGLISH: Wordlist = Wordlist::new(&WORDLIST, [70, 50, 50, 30]);
+
+/// Second English list (`WORDLIST_V2`): the V1 words plus common nouns, verbs,
+/// adjectives and adverbs, mostly from the BIP-39 English list (CC0-1.0) and the
+/// word sources of `data/english-words.txt`. Each group is sorted, which the
+/// compile-time duplicate check relies on.
+pub static WORDLIST_2048: [&str; 2048] = [
+    // Nouns (1000)
+    "access","account","achiever","acid","action","activity","actor","addition","adult","advice",
+    "affair","agent","airplane","airport","aisle","alarm","album","alley","alpha","amount",
+    "anchor","anger","angle","animal","ankle","answer","apparel","apple","approval","arch",
+    "area","arena","argument","armor","army","arrow","artist","aspect","asset","atom",
+    "audit","aunt","author","autumn","axis","baby","back","bacon","badge","bait",
+    "balance","ball","balloon","bamboo","banana","band","banner","barrel","base","baseball",
+    "basin","basket","bath","battle","beach","bead","beam","bean","bear","beast",
+    "beauty","bedroom","beef","beetle","beggar","beginner","behavior","belief","bell","belt",
+    "bench","berry","bike","bird","birth","birthday","blade","blast","blood","blouse",
+    "board","boat","body","bomb","bone","bonus","book","boot","border","boss",
+    "bottle","bottom","boundary","brain","brake","branch","brand","brass","bread","breath",
+    "breeze","brick","bridge","bronze","broom","brother","brush","bubble","bucket","buddy",
+    "budget","building","bulb","bulk","bundle","bunker","burden","burger","business","butter",
+    "button","buyer","buzz","cabbage","cabin","cable","cactus","cage","cake","calendar",
+    "camera","camp","canal","candy","cannon","canoe","canvas","canyon","caption","carbon",
+    "card","cargo","carpet","carriage","cart","case","cash","castle","cattle","cave",
+    "celery","cellar","cement","cemetery","census","cent","cereal","chain","chair","chalk",
+    "chance","channel","chaos","chapter","cheese","chef","cherry","chess","chest","chicken",
+    "child","chin","choice","chunk","church","circle","city","clam","class","claw",
+    "clay","clerk","click","client","cliff","clinic","clip","clock","cloth","cloud",
+    "clover","clown","club","clump","clutch","coach","coal","coast","coat","cobweb",
+    "code","coffee","coil","coin","collar","color","column","comb","comfort","company",
+    "control","cook","copper","coral","cord","core","cork","corn","cost","cotton",
+    "couch","country","couple","course","cousin","coyote","cracker","cradle","crane","crate",
+    "crater","crayon","cream","creator","creature","credit","creek","crew","crib","crime",
+    "critic","crop","crow","crowd","crown","cruise","cube","culture","current","curtain",
+    "curve","cushion","custom","danger","dash","daughter","dawn","death","debris","debt",
+    "decade","decision","deer","degree","demise","denial","depth","deputy","desert","design",
+    "desk","detail","device","dial","diary","dice","diesel","diet","dinner","dirt",
+    "disease","disgust","dish","distance","division","dock","doctor","doll","domain","donkey",
+    "donor","door","dose","dove","downtown","draft","dragon","drama","drawer","dream",
+    "drum","duck","dune","dust","duty","dwarf","eagle","earth","east","edge",
+    "effect","effort","elbow","element","elite","enemy","energy","engine","enough","entry",
+    "error","essay","estate","evening","event","example","exchange","exile","exit","expert",
+    "face","fact","faith","fame","family","fang","farm","farmer","father","faucet",
+    "fault","feast","feather","feeling","fence","fever","fiber","fiction","field","file",
+    "finger","fire","fireman","fish","flag","flame","flavor","flesh","flight","flock",
+    "floor","flower","foam","foil","food","foot","forest","fork","forum","frame",
+    "freedom","friction","friend","frog","front","frost","fruit","fuel","fury","future",
+    "game","garden","gate","gauge","genre","ghost","giant","gift","giraffe","girl",
+    "glass","globe","gloom","glory","glove","glue","goat","gold","goldfish","goose",
+    "governor","gown","grace","grade","grain","grape","grass","grid","grief","grit",
+    "ground","group","growth","guilt","guitar","habit","hair","haircut","half","hall",
+    "hammer","hand","harbor","harmony","hate","hawk","head","health","hearing","heart",
+    "heat","hero","hill","history","hobby","hole","holiday","home","honey","hood",
+    "hook","horn","horse","hose","hospital","hotel","hour","house","humor","hydrant",
+    "icon","idea","image","impulse","inch","income","index","industry","input","insect",
+    "interest","iron","island","issue","item","ivory","jail","jazz","jeans","jelly",
+    "jewel","joke","journey","juice","junk","kettle","kite","kitten","kitty","kiwi",
+    "knee","knife","knot","labor","laborer","lace","lady","ladybug","lake","lamp",
+    "land","language","lava","lawn","lawyer","layer","leaf","learning","leather","lemon",
+    "lens","letter","lettuce","level","library","life","light","limb","line","linen",
+    "lion","list","loaf","loan","locket","logic","loop","lunch","machine","magic",
+    "maid","mail","mailbox","manager","mango","maple","marble","mark","market","mask",
+    "mass","match","math","maze","meal","meat","medal","media","meeting","memory",
+    "menu","mercy","merit","mesh","metal","method","middle","milk","mind","minister",
+    "mint","minute","mist","mitten","model","moment","money","monkey","month","moon",
+    "morning","mother","motion","motor","mountain","mouse","mouth","movie","mule","muscle",
+    "music","myth","nail","name","nation","nature","neck","needle","nerve","nest",
+    "news","night","noise","north","nose","note","notebook","number","nurse","oatmeal",
+    "ocean","odor","office","olive","onion","opera","opinion","orange","orbit","organ",
+    "ornament","oval","oven","owner","ozone","pact","page","pail","pain","pair",
+    "palm","pancake","panda","panel","panic","paper","parcel","parent","park","part",
+    "partner","party","paste","patch","path","pattern","payment","peace","pear","pencil",
+    "person","pest","phone","photo","piano","pickle","picture","piece","pill","pilot",
+    "pipe","pitch","pizza","place","plane","plant","plastic","plate","pleasure","plot",
+    "plough","pocket","poem","poet","point","poison","pole","police","pond","pony",
+    "pool","popcorn","porter","position","potato","powder","power","price","pride","prison",
+    "prize","process","profit","proof","property","prose","pulp","pulse","pump","pupil",
+    "puppy","purpose","purse","quarter","quartz","queen","question","quilt","quiver","quiz",
+    "rabbit","rack","radar","radio","rail","railway","rain","raise","rake","rally",
+    "ramp","ranch","range","rate","raven","razor","reaction","reading","reason","rebel",
+    "receipt","record","relation","religion","rhythm","rice","riddle","ridge","rifle","ring",
+    "rival","river","road","robin","robot","rock","roof","room","root","rose",
+    "route","sack","salad","salon","salt","sand","sauce","scale","scarf","scene",
+    "scent","school","science","scout","scrap","screw","seashore","season","seat","seed",
+    "sense","servant","setup","shade","shadow","shaft","shame","shed","sheep","sheet",
+    "shelf","shell","shelter","ship","shirt","shoe","shop","side","sidewalk","sight",
+    "sign","signal","silence","silk","silver","siren","sister","size","skate","skill",
+    "skin","skirt","skull","slab","slam","sleet","slice","slope","slot","slush",
+    "smoke","snack","snail","snake","snow","soap","society","sock","soda","sofa",
+    "song","soul","sound","soup","south","space","spade","spark","speed","spice",
+    "spike","spirit","sponge","spoon","sport","spot","spring","square","squirrel","staff",
+    "stage","stamp","star","station","steak","steam","steel","stem","step","stew",
+    "stick","stitch","stock","stocking","stomach","stone","stool","store","storm","story",
+    "stove","stranger","straw","stream","street","string","style","sugar","suit","summer",
+    "surface","surge","surprise","swamp","swarm","sweater","sword","syrup","system","table",
+    "tail","tank","tape","task","taxi","teaching","team","temper","tendency","tent",
+    "term","text","texture","theme","theory","thing","thought","thread","thrill","throat",
+    "throne","thumb","thunder","ticket","tide","tiger","tilt","time","title","toad",
+    "token","tone","tongue","tool","tooth","topic","torch","total","tower","town",
+    "track","trail","trash","tray","tree","trend","trial","tribe","trick","trip",
+    "trouble","trousers","truck","truth","tube","tuna","turkey","twig","twin","umbrella",
+    "uncle","unit","usage","vacation","value","valve","vapor","vase","vault","veil",
+    "vein","venue","verb","verse","vessel","vest","video","view","virus","visa",
+    "visitor","voice","void","volcano","voyage","wage","wagon","wall","wasp","water",
+    "wealth","weather","week","weight","west","whale","wheat","wheel","whip","width",
+    "wife","wind","window","wine","wing","winter","wire","wolf","woman","wood",
+    "wool","word","world","worm","wound","wren","wrench","wrist","writer","writing",
+    "yard","yarn","year","yoke","youth","zebra","zephyr","zinc","zipper","zone",
+    // Verbs (660)
+    "accept","achieve","acquire","adapt","adjust","admire","admit","adopt","advance","afford",
+    "agree","allow","alter","amaze","amuse","announce","annoy","appear","apply","approve",
+    "argue","arrange","arrest","arrive","assess","assign","assist","assume","assure","attach",
+    "attack","attain","attempt","attend","attract","avoid","await","awake","award","bake",
+    "bang","bathe","beat","become","begin","behave","believe","belong","bend","benefit",
+    "bind","bite","blame","blend","bless","blink","block","bloom","blossom","blow",
+    "blush","boast","boil","bolt","boost","borrow","bother","bounce","break","breed",
+    "brew","bring","browse","build","bump","burn","burst","bury","call","calm",
+    "cancel","care","carry","carve","cast","catch","cause","cease","change","charge",
+    "charm","chase","chat","cheat","check","cheer","chew","choke","choose","chop",
+    "claim","clap","clean","clear","climb","cling","close","collect","come","commit",
+    "compare","complete","conduct","confirm","connect","consider","contain","continue","convey","convince",
+    "cool","cope","copy","correct","cough","count","cover","crack","craft","crash",
+    "crawl","create","creep","cross","crush","cure","curl","cycle","damage","dance",
+    "dare","deal","debate","decay","decide","decline","decorate","decrease","defeat","defend",
+    "define","delay","delete","deliver","demand","deny","depart","depend","deposit","derive",
+    "describe","desire","destroy","detect","develop","devote","differ","digest","direct","disagree",
+    "discover","dismiss","display","dive","divide","donate","double","doubt","drag","drain",
+    "draw","dress","drift","drill","drink","drip","drive","drop","drown","dump",
+    "dwell","earn","ease","echo","edit","educate","elect","embrace","emerge","emit",
+    "employ","empty","enable","endure","enforce","engage","enhance","enjoy","enrich","enroll",
+    "ensure","enter","escape","evolve","exceed","excite","excuse","execute","exhale","exhibit",
+    "exist","expand","expect","explain","explore","export","expose","express","extend","fade",
+    "fail","fall","fasten","fear","feed","feel","fetch","fight","fill","film",
+    "find","finish","flash","flee","float","flood","flow","flush","focus","fold",
+    "follow","fool","forbid","force","forget","form","found","free","freeze","fulfil",
+    "gain","gallop","gamble","gasp","gather","gaze","giggle","give","glance","glare",
+    "glide","glow","govern","grab","grant","grasp","greet","grin","grind","grip",
+    "groan","grow","growl","guard","guess","guide","handle","hang","happen","harm",
+    "haul","heal","hear","help","hide","hike","hint","hire","hold","hope",
+    "host","hover","howl","hunt","hurry","hurt","identify","ignore","imagine","imitate",
+    "improve","include","increase","indicate","inform","inhale","inherit","inject","injure","insert",
+    "insist","inspire","install","invest","invite","involve","isolate","itch","join","judge",
+    "jump","keep","kick","kill","kiss","kneel","knit","knock","know","label",
+    "last","laugh","lead","lean","leap","learn","leave","lend","lick","lift",
+    "like","limit","link","listen","live","load","lock","look","lose","love",
+    "lower","make","manage","march","marry","matter","measure","meet","melt","mend",
+    "mention","merge","miss","mistake","moan","modify","mount","mourn","move","multiply",
+    "need","neglect","notice","obey","object","oblige","observe","obtain","occur","offer",
+    "omit","open","oppose","order","pack","paddle","paint","pass","pause","peel",
+    "permit","pick","pinch","plan","play","plead","please","plug","polish","post",
+    "pray","predict","prefer","prepare","present","press","prevent","print","produce","promote",
+    "protect","protest","prove","provide","pull","punch","purchase","push","queue","quit",
+    "quote","race","rank","reach","react","read","rebuild","recall","receive","recycle",
+    "reduce","reflect","refuse","regret","reject","relax","release","rely","remain","remember",
+    "remind","remove","render","renew","rent","repair","repeat","replace","reply","report",
+    "request","require","rescue","resemble","resist","respect","rest","retire","retreat","return",
+    "reveal","review","reward","ride","rinse","risk","roar","roast","roll","ruin",
+    "rule","rush","sail","satisfy","save","scare","scatter","scold","search","seek",
+    "seize","select","sell","send","separate","serve","settle","shake","shape","share",
+    "shave","shift","shine","shiver","shock","shoot","shout","show","shrug","shut",
+    "sigh","sing","sink","sketch","skip","slap","sleep","slide","slip","smash",
+    "smell","smile","snap","sneeze","sniff","snore","soak","soar","solve","sort",
+    "spare","speak","spell","spend","spill","spin","spit","split","spoil","spray",
+    "spread","squeeze","stack","stain","stand","stare","start","state","stay","steal",
+    "steer","sting","stir","stop","stretch","strike","strip","study","stuff","submit",
+    "suck","suffer","suggest","supply","support","suppose","surround","suspect","swallow","swap",
+    "sway","swear","sweat","sweep","swell","swim","swing","switch","tackle","talk",
+    "tame","taste","teach","tear","tease","tell","tempt","tend","test","thank",
+    "thaw","think","throw","tick","tidy","tire","toast","toss","touch","trace",
+    "trade","train","transfer","trap","travel","tread","treat","trim","trot","trust",
+    "tumble","turn","twist","type","undo","unfold","unite","unlock","untie","update",
+    "upgrade","upset","urge","utter","vanish","vary","visit","vote","wade","wait",
+    "wake","walk","wander","want","warn","wash","waste","watch","wave","wear",
+    "weave","weep","weigh","welcome","whisper","whistle","wink","wipe","wish","wonder",
+    "work","worry","wrap","wreck","wrestle","write","yawn","yell","yield","zoom",
+    // Adjectives (280)
+    "able","absent","abstract","absurd","active","aerobic","afraid","alert","alive","alone",
+    "amazing","amused","ancient","angry","annual","antique","arctic","armed","aware","awesome",
+    "awful","awkward","basic","better","bitter","black","bleak","blind","blue","boring",
+    "brave","brief","bright","brisk","broken","brown","busy","capable","careful","casual",
+    "central","certain","cheap","chief","chronic","civil","clever","comic","common","cosmic",
+    "crazy","crisp","cruel","curious","cute","damp","dear","deep","digital","distant",
+    "dizzy","drastic","dumb","dynamic","eager","easy","elder","electric","elegant","endless",
+    "entire","equal","evil","exact","exotic","extra","faint","fair","false","famous",
+    "fancy","fast","fatal","favorite","federal","female","fierce","final","fine","firm",
+    "first","fiscal","flat","fluid","formal","fragile","frequent","fresh","frozen","full",
+    "funny","general","gentle","glad","global","golden","good","grand","great","green",
+    "handy","happy","hard","harsh","heavy","hidden","high","hollow","honest","huge",
+    "humble","hungry","hybrid","idle","immense","indoor","initial","inner","innocent","insane",
+    "intact","jealous","junior","just","keen","kind","large","lazy","legal","liquid",
+    "little","local","lonely","long","loud","loyal","lucky","lunar","main","major",
+    "marine","mean","mental","merry","minor","modern","moral","naive","narrow","nasty",
+    "native","natural","neat","negative","next","nice","noble","normal","novel","nuclear",
+    "obscure","obvious","online","ordinary","original","outdoor","outer","perfect","physical","pink",
+    "polar","possible","pretty","private","proper","proud","public","quick","quiet","random",
+    "rapid","rare","ready","real","regular","remote","rich","right","rigid","robust",
+    "rough","round","royal","rude","rural","sacred","safe","secret","secure","senior",
+    "shallow","sharp","short","sick","silent","silly","similar","simple","slender","slight",
+    "slim","slow","small","smart","smooth","social","soft","solar","solid","spatial",
+    "special","stable","steady","strange","strong","subtle","sudden","sunny","super","supreme",
+    "sure","sweet","swift","tender","tight","tiny","tired","tragic","true","typical",
+    "ugly","unable","unaware","unfair","unhappy","unique","unknown","unusual","upper","urban",
+    "useful","useless","usual","vacant","vague","various","vast","vibrant","vicious","virtual",
+    "visual","vivid","warm","weird","wide","wild","wise","wrong","yellow","young",
+    // Adverbs (108)
+    "actually","after","again","ahead","almost","along","already","also","always","apart",
+    "around","aside","away","awfully","badly","barely","blindly","briefly","broadly","cheaply",
+    "cleanly","clearly","closely","daily","deeply","eagerly","early","easily","equally","evenly",
+    "exactly","fairly","finally","firmly","forward","frankly","freely","fully","gently","gladly",
+    "greatly","happily","hardly","heavily","hence","highly","hugely","humbly","ideally","jointly",
+    "kindly","largely","lately","later","legally","lightly","locally","loosely","loudly","luckily",
+    "mainly","maybe","merely","mildly","monthly","morally","mostly","namely","nearly","neatly",
+    "never","newly","nicely","oddly","often","once","only","openly","overly","partly",
+    "poorly","purely","quite","rarely","rather","really","sadly","safely","simply","since",
+    "slowly","solely","soon","still","subtly","surely","today","tomorrow","tonight","truly",
+    "twice","vastly","very","weekly","wholly","widely","wildly","yearly",
+];
+
+/// `WORDLIST_2048` with its part-of-speech groups.
+pub static ENGLISH_2048: Wordlist = Wordlist::new(&WORDLIST_2048, [1000, 660, 280, 108]);
+
+/// English list for `MineState::wordlist_version` (unknown versions use the latest).
+pub fn english(version: u8) -> &'static Wordlist {
+    match version {
+        0 | WORDLIST_V1 => &ENGLISH,
+        _ => &ENGLISH_2048,
+    }
+}
+
+/// Word category; wordlists store the groups in this order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+}
+
+impl PartOfSpeech {
+    pub const ALL: [PartOfSpeech; 4] = [Self::Noun, Self::Verb, Self::Adjective, Self::Adverb];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Noun => "noun",
+            Self::Verb => "verb",
+            Self::Adjective => "adjective",
+            Self::Adverb => "adverb",
+        }
+    }
+}
+
+/// Read access to a wordlist stored as consecutive part-of-speech groups,
+/// built in (`Wordlist`) or on-chain (`PackedWordlist`).
+pub trait WordSource {
+    fn len(&self) -> usize;
+
+    /// Bytes of word `index` (< `len()`).
+    fn word(&self, index: usize) -> &[u8];
+
+    /// Group sizes, in `PartOfSpeech` order; they sum to `len()`.
+    fn groups(&self) -> [usize; 4];
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Indices of the `pos` group.
+    fn range(&self, pos: PartOfSpeech) -> Range<usize> {
+        let groups = self.groups();
+        let start: usize = groups[..pos as usize].iter().sum();
+        start..start + groups[pos as usize]
+    }
+
+    /// Part of speech of word `index`.
+    fn part_of_speech(&self, index: usize) -> PartOfSpeech {
+        let groups = self.groups();
+        let mut end = 0;
+        for pos in PartOfSpeech::ALL {
+            end += groups[pos as usize];
+            if index < end {
+                return pos;
+            }
+        }
+        PartOfSpeech::Adverb
+    }
+}
+
+/// A built-in wordlist.
+pub struct Wordlist {
+    pub words: &'static [&'static str],
+    pub groups: [usize; 4], // group sizes, in `PartOfSpeech` order
+}
+
+/// Lists up to this size are checked for duplicates pairwise; longer ones
+/// must keep each part-of-speech group sorted so the check stays cheap.
+const PAIRWISE_CHECK_MAX: usize = 256;
+
+impl Wordlist {
+    /// Fails to compile (in a `const`) unless the group sizes cover `words`
+    /// and every word is unique and passes `is_valid_word`.
+    pub const fn new(words: &'static [&'static str], groups: [usize; 4]) -> Self {
+        assert!(groups[0] + groups[1] + groups[2] + groups[3] == words.len(), "part-of-speech groups must cover the wordlist");
+        let mut i = 0;
+        while i < words.len() {
+            assert!(is_valid_word(words[i].as_bytes()), "word must be lowercase, MIN_WORD_CHARS characters to MAX_WORD_LEN bytes");
+            i += 1;
+        }
+        assert!(all_unique(words, groups), "duplicate word in wordlist");
+        Wordlist { words, groups }
+    }
+}
+
+impl WordSource for Wordlist {
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    fn word(&self, index: usize) -> &[u8] {
+        self.words[index].as_bytes()
+    }
+
+    fn groups(&self) -> [usize; 4] {
+        self.groups
+    }
+}
+
+/// Whether a wordlist may hold `word`: MIN_WORD_CHARS characters to
+/// MAX_WORD_LEN bytes, lowercase (ASCII bytes a-z; accented letters are the
+/// pack's business, see `lang`).
+pub const fn is_valid_word(word: &[u8]) -> bool {
+    if word.len() > MAX_WORD_LEN {
+        return false;
+    }
+    let mut chars = 0;
+    let mut j = 0;
+    while j < word.len() {
+        if word[j] < 0x80 && !word[j].is_ascii_lowercase() {
+            return false;
+        }
+        if word[j] & 0xC0 != 0x80 {
+            chars += 1;
+        }
+        j += 1;
+    }
+    chars >= MIN_WORD_CHARS
+}
+
+/// A wordlist laid out as MAX_WORD_LEN-byte slots, each word zero-padded:
+/// the words of an on-chain `WordList` account.
+pub struct PackedWordlist<'a> {
+    slots: &'a [u8],
+    groups: [usize; 4],
+}
+
+impl<'a> PackedWordlist<'a> {
+    /// `slots.len()` is rounded down to whole slots.
+    pub fn new(slots: &'a [u8], groups: [usize; 4]) -> Self {
+        PackedWordlist { slots: &slots[..slots.len() - slots.len() % MAX_WORD_LEN], groups }
+    }
+
+    /// keccak256 of the group sizes (u16 LE each) followed by the slots; what
+    /// governance approves and `seal_word_list` checks.
+    pub fn hash(&self) -> [u8; 32] {
+        let mut groups = [0u8; 8];
+        for (g, &size) in groups.chunks_exact_mut(2).zip(self.groups.iter()) {
+            g.copy_from_slice(&(size as u16).to_le_bytes());
+        }
+        keccak::hashv(&[&groups, self.slots])
+    }
+
+    /// The groups are non-empty and cover the slots, every word is ASCII and
+    /// passes `is_valid_word`, padding is zero, and each group is strictly
+    /// ascending. Sorting rules out duplicates within a group; across groups
+    /// they are not checked (too costly on-chain), so review before sealing.
+    pub fn is_valid(&self) -> bool {
+        if self.groups.contains(&0) || self.groups.iter().sum::<usize>() != self.len() {
+            return false;
+        }
+        for i in 0..self.len() {
+            let slot = &self.slots[i * MAX_WORD_LEN..(i + 1) * MAX_WORD_LEN];
+            let word = self.word(i);
+            if !word.is_ascii() || !is_valid_word(word) || slot[word.len()..].iter().any(|&b| b != 0) {
+                return false;
+            }
+        }
+        PartOfSpeech::ALL.iter().all(|&pos| {
+            let range = self.range(pos);
+            (range.start + 1..range.end).all(|i| compare(self.word(i - 1), self.word(i)).is_lt())
+        })
+    }
+}
+
+impl WordSource for PackedWordlist<'_> {
+    fn len(&self) -> usize {
+        self.slots.len() / MAX_WORD_LEN
+    }
+
+    fn word(&self, index: usize) -> &[u8] {
+        let slot = &self.slots[index * MAX_WORD_LEN..(index + 1) * MAX_WORD_LEN];
+        let len = slot.iter().position(|&b| b == 0).unwrap_or(MAX_WORD_LEN);
+        &slot[..len]
+    }
+
+    fn groups(&self) -> [usize; 4] {
+        self.groups
+    }
+}
+
+/// Byte-wise order of two words.
+const fn compare(a: &[u8], b: &[u8]) -> core::cmp::Ordering {
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return if a[i] < b[i] { core::cmp::Ordering::Less } else { core::cmp::Ordering::Greater };
+        }
+        i += 1;
+    }
+    if a.len() < b.len() {
+        core::cmp::Ordering::Less
+    } else if a.len() > b.len() {
+        core::cmp::Ordering::Greater
+    } else {
+        core::cmp::Ordering::Equal
+    }
+}
+
+/// Duplicate check for `Wordlist::new`: pairwise for short lists, otherwise
+/// strictly ascending groups plus a binary search of each word in the others.
+const fn all_unique(words: &[&str], groups: [usize; 4]) -> bool {
+    if words.len() <= PAIRWISE_CHECK_MAX {
+        let mut i = 0;
+        while i < words.len() {
+            let mut j = 0;
+            while j < i {
+                if compare(words[i].as_bytes(), words[j].as_bytes()).is_eq() {
+                    return false;
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        return true;
+    }
+
+    let mut starts = [0usize; 5];
+    let mut g = 0;
+    while g < 4 {
+        starts[g + 1] = starts[g] + groups[g];
+        let mut i = starts[g] + 1;
+        while i < starts[g + 1] {
+            assert!(compare(words[i - 1].as_bytes(), words[i].as_bytes()).is_lt(), "long wordlist groups must be sorted");
+            i += 1;
+        }
+        g += 1;
+    }
+    let mut i = 0;
+    while i < words.len() {
+        let mut g = 0;
+        while g < 4 {
+            if !(starts[g] <= i && i < starts[g + 1]) {
+                let (mut lo, mut hi) = (starts[g], starts[g + 1]);
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    match compare(words[mid].as_bytes(), words[i].as_bytes()) {
+                        core::cmp::Ordering::Less => lo = mid + 1,
+                        core::cmp::Ordering::Greater => hi = mid,
+                        core::cmp::Ordering::Equal => return false,
+                    }
+                }
+            }
+            g += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Grammatical patterns for `derive_pattern_words_from`, repeated when more
+/// words are required than the pattern has.
+pub const PATTERNS: [&[PartOfSpeech]; 4] = {
+    use PartOfSpeech::*;
+    [
+        &[Adjective, Noun, Verb],
+        &[Noun, Verb, Adverb],
+        &[Adjective, Noun, Verb, Adverb],
+        &[Noun, Verb, Adjective, Noun],
+    ]
+};
+
+/// Derived required words (fixed-size, no heap).
+#[derive(Clone, Copy)]
+pub struct RequiredWords {
+    pub words: [[u8; MAX_WORD_LEN]; MAX_REQUIRED],
+    pub lens: [usize; MAX_REQUIRED],
+    pub parts: [PartOfSpeech; MAX_REQUIRED],
+    pub count: usize,
+}
+
+impl RequiredWords {
+    pub const EMPTY: RequiredWords = RequiredWords {
+        words: [[0; MAX_WORD_LEN]; MAX_REQUIRED],
+        lens: [0; MAX_REQUIRED],
+        parts: [PartOfSpeech::Noun; MAX_REQUIRED],
+        count: 0,
+    };
+}
+
+/// Highest value `difficulty_tier` returns.
+pub const MAX_DIFFICULTY_TIER: u8 = 5;
+
+/// Map difficulty to a tier 0..=MAX_DIFFICULTY_TIER.
+/// Drives both the required word count and the scaled text rules
+/// (`verify::TextRules::for_difficulty`).
+pub fn difficulty_tier(difficulty: u64) -> u8 {
+    if difficulty <= 10 { 0 }
+    else if difficulty <= 15 { 1 }
+    else if difficulty <= 20 { 2 }
+    else if difficulty <= 30 { 3 }
+    else if difficulty <= 40 { 4 }
+    else { MAX_DIFFICULTY_TIER }
+}
+
+/// Map difficulty to required word count.
+pub fn word_count_for_difficulty(difficulty: u64) -> usize {
+    3 + difficulty_tier(difficulty) as usize
+}
+
+/// Word count at `difficulty` plus `extra` (`TextRules::extra_words`), capped
+/// at what `version` can derive.
+pub fn word_count(difficulty: u64, extra: u8, version: u8) -> usize {
+    let max = if version >= DERIVATION_V2 { MAX_REQUIRED } else { V1_MAX_REQUIRED };
+    (word_count_for_difficulty(difficulty) + extra as usize).min(max)
+}
+
+/// Derive required words deterministically from challenge seed and difficulty.
+pub fn derive_words(seed: &[u8; 32], difficulty: u64) -> RequiredWords {
+    derive_words_from(&ENGLISH, seed, difficulty)
+}
+
+/// `derive_words` over another wordlist (e.g. a `lang::LanguagePack`'s).
+pub fn derive_words_from(list: &dyn WordSource, seed: &[u8; 32], difficulty: u64) -> RequiredWords {
+    derive_words_versioned(list, seed, word_count_for_difficulty(difficulty), DERIVATION_V1)
+}
+
+/// `count` words (at most MAX_REQUIRED; V1 at most V1_MAX_REQUIRED) by
+/// derivation `version`.
+pub fn derive_words_versioned(list: &dyn WordSource, seed: &[u8; 32], count: usize, version: u8) -> RequiredWords {
+    derive(list, seed, count, None, version)
+}
+
+/// Like `derive_words_versioned`, but word i is drawn from the part-of-speech
+/// group `PATTERNS[pattern][i % len]`. Returns the pattern index too.
+pub fn derive_pattern_words_from(list: &dyn WordSource, seed: &[u8; 32], count: usize, version: u8) -> (usize, RequiredWords) {
+    let pattern = seed[20] as usize % PATTERNS.len();
+    (pattern, derive(list, seed, count, Some(PATTERNS[pattern]), version))
+}
+
+/// Byte stream for V2 derivation: block k is
+/// `keccak256("poi-words" || seed || k as u32 LE)`, read in order.
+pub struct WordStream<'a> {
+    seed: &'a [u8; 32],
+    block: [u8; 32],
+    counter: u32,
+    pos: usize,
+}
+
+impl<'a> WordStream<'a> {
+    pub fn new(seed: &'a [u8; 32]) -> Self {
+        WordStream { seed, block: [0; 32], counter: 0, pos: 32 }
+    }
+
+    /// Next 4 bytes as a big-endian u32.
+    pub fn next_u32(&mut self) -> u32 {
+        if self.pos == 32 {
+            self.block = keccak::hashv(&[STREAM_TAG, self.seed, &self.counter.to_le_bytes()]);
+            self.counter += 1;
+            self.pos = 0;
+        }
+        let b = &self.block[self.pos..self.pos + 4];
+        self.pos += 4;
+        u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+    }
+
+    /// Uniform in 0..n (n > 0): draws at or above the largest multiple of n
+    /// that fits in a u32 are rejected, so no residue is favoured.
+    pub fn below(&mut self, n: u32) -> u32 {
+        let zone = u32::MAX - (u32::MAX % n + 1) % n; // last accepted draw
+        loop {
+            let x = self.next_u32();
+            if x <= zone {
+                return x % n;
+            }
+        }
+    }
+}
+
+/// Pick `count` distinct words from the candidate range (the whole list, or
+/// the pattern's group for word i). V1 takes `seed[2i..2i + 2]` modulo the
+/// range and probes past duplicates; V2 draws uniformly from `WordStream`
+/// and redraws duplicates. Stops early when a range runs out of words.
+fn derive(list: &dyn WordSource, seed: &[u8; 32], count: usize, pattern: Option<&[PartOfSpeech]>, version: u8) -> RequiredWords {
+    let v2 = version >= DERIVATION_V2;
+    let count = count.min(if v2 { MAX_REQUIRED } else { V1_MAX_REQUIRED });
+    let mut result = RequiredWords { count, ..RequiredWords::EMPTY };
+    let mut stream = WordStream::new(seed);
+
+    // Indices picked so far (at most MAX_REQUIRED, so a scan beats a bitmap)
+    let mut used = [usize::MAX; MAX_REQUIRED];
+
+    let mut i = 0;
+    while i < count {
+        let range = match pattern {
+            Some(p) => list.range(p[i % p.len()]),
+            None => 0..list.len(),
+        };
+        let size = range.len();
+        let taken = used[..i].iter().filter(|&&u| range.contains(&u)).count();
+        if size <= taken {
+            result.count = i;
+            break;
+        }
+
+        let idx = if v2 {
+            loop {
+                let idx = range.start + stream.below(size as u32) as usize;
+                if !used[..i].contains(&idx) {
+                    break idx;
+                }
+            }
+        } else {
+            let raw = ((seed[i * 2] as u16) << 8) | (seed[i * 2 + 1] as u16);
+            let mut idx = (raw as usize) % size;
+            // Skip duplicates
+            while used[..i].contains(&(range.start + idx)) {
+                idx = (idx + 1) % size;
+            }
+            range.start + idx
+        };
+
+        used[i] = idx;
+        let word = list.word(idx);
+        let len = word.len(); // ≤ MAX_WORD_LEN, checked by `is_valid_word`
+        result.words[i][..len].copy_from_slice(word);
+        result.lens[i] = len;
+        result.parts[i] = list.part_of_speech(idx);
+
+        i += 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed() -> [u8; 32] {
+        let mut seed = [0u8; 32];
+        for (i, b) in seed.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37);
+        }
+        seed
+    }
+
+    fn as_strs(rw: &RequiredWords) -> Vec<&str> {
+        (0..rw.count).map(|i| core::str::from_utf8(&rw.words[i][..rw.lens[i]]).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_below() {
+        let seed = seed();
+        let mut stream = WordStream::new(&seed);
+        let mut counts = [0u32; 6];
+        for _ in 0..6000 {
+            counts[stream.below(6) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| (850..=1150).contains(&c)), "{counts:?}");
+        // n = 1 and n = u32::MAX still terminate
+        assert_eq!(stream.below(1), 0);
+        assert!(stream.below(u32::MAX) < u32::MAX);
+    }
+
+    #[test]
+    fn test_wordlist_versions() {
+        assert_eq!(english(0).len(), WORDLIST_SIZE);
+        assert_eq!(english(WORDLIST_V1).len(), WORDLIST_SIZE);
+        assert_eq!(english(WORDLIST_V2).len(), 2048);
+        assert_eq!(ENGLISH_2048.range(PartOfSpeech::Adverb), 1940..2048);
+        // V2 keeps every V1 word, in the same part of speech
+        for (i, w) in WORDLIST.iter().enumerate() {
+            let j = WORDLIST_2048.iter().position(|x| x == w).unwrap_or_else(|| panic!("{w} missing"));
+            assert_eq!(ENGLISH.part_of_speech(i), ENGLISH_2048.part_of_speech(j), "{w}");
+        }
+    }
+
+    fn pack(list: &Wordlist) -> Vec<u8> {
+        let mut slots = vec![0u8; list.len() * MAX_WORD_LEN];
+        for (slot, w) in slots.chunks_exact_mut(MAX_WORD_LEN).zip(list.words.iter()) {
+            slot[..w.len()].copy_from_slice(w.as_bytes());
+        }
+        slots
+    }
+
+    #[test]
+    fn test_packed_wordlist() {
+        let mut slots = pack(&ENGLISH_2048);
+        let packed = PackedWordlist::new(&slots, ENGLISH_2048.groups);
+        assert!(packed.is_valid());
+        assert_eq!(packed.len(), 2048);
+        assert_eq!(packed.range(PartOfSpeech::Adverb), ENGLISH_2048.range(PartOfSpeech::Adverb));
+
+        // Same words as the built-in list, whatever the derivation
+        let seed = seed();
+        for version in [DERIVATION_V1, DERIVATION_V2] {
+            let a = derive_words_versioned(&ENGLISH_2048, &seed, 8, version);
+            let b = derive_words_versioned(&packed, &seed, 8, version);
+            assert_eq!(as_strs(&a), as_strs(&b));
+            let (_, a) = derive_pattern_words_from(&ENGLISH_2048, &seed, 8, version);
+            let (_, b) = derive_pattern_words_from(&packed, &seed, 8, version);
+            assert_eq!(as_strs(&a), as_strs(&b));
+        }
+
+        // The hash covers the grouping as well as the words
+        let hash = packed.hash();
+        assert_ne!(hash, PackedWordlist::new(&slots, [1001, 659, 280, 108]).hash());
+        assert!(!PackedWordlist::new(&slots, [1000, 660, 280, 107]).is_valid());
+        assert!(!PackedWordlist::new(&slots[..1000 * MAX_WORD_LEN], [1000, 0, 0, 0]).is_valid());
+
+        // V1 isn't sorted, so it must be re-sorted before going on-chain
+        assert!(!PackedWordlist::new(&pack(&ENGLISH), ENGLISH.groups).is_valid());
+
+        // Uppercase, short and badly padded words are rejected
+        for bad in [&b"Acid\0\0\0\0"[..], b"ace\0\0\0\0\0", b"acid\0x\0\0"] {
+            slots[..MAX_WORD_LEN].copy_from_slice(bad);
+            assert!(!PackedWordlist::new(&slots, ENGLISH_2048.groups).is_valid());
+        }
+    }
+
+    #[test]
+    fn test_derivation_versions() {
+        let seed = seed();
+        // A stored 0 is V1
+        let v1 = derive_words_versioned(&ENGLISH, &seed, 8, DERIVATION_V1);
+        assert_eq!(as_strs(&v1), as_strs(&derive_words_versioned(&ENGLISH, &seed, 8, 0)));
+        assert_eq!(as_strs(&v1), as_strs(&derive_words(&seed, 50)));
+
+        // V1 is capped by the seed; V2 goes to MAX_REQUIRED
+        assert_eq!(derive_words_versioned(&ENGLISH, &seed, MAX_REQUIRED, DERIVATION_V1).count, V1_MAX_REQUIRED);
+        let v2 = derive_words_versioned(&ENGLISH, &seed, MAX_REQUIRED, DERIVATION_V2);
+        let words = as_strs(&v2);
+        assert_eq!(words.len(), MAX_REQUIRED);
+        assert!((0..words.len()).all(|i| !words[..i].contains(&words[i])), "{words:?}");
+        assert_eq!(
+            words[..4],
+            ["wheel", "barely", "stone", "twice"],
+            "V2 stream changed; clients depend on it"
+        );
+        assert_eq!(word_count(50, 4, DERIVATION_V2), 12);
+        assert_eq!(word_count(50, 4, DERIVATION_V1), V1_MAX_REQUIRED);
+
+        // A group smaller than the pattern asks for stops the derivation
+        static TINY: Wordlist = Wordlist::new(&["apple", "river", "think", "quiet", "often"], [2, 1, 1, 1]);
+        let mut seed = seed;
+        seed[20] = 1; // noun, verb, adverb, …
+        let (_, rw) = derive_pattern_words_from(&TINY, &seed, 8, DERIVATION_V2);
+        assert_eq!(rw.count, 4, "the second verb has nothing left to draw");
+        assert_eq!(rw.parts[..4], [PartOfSpeech::Noun, PartOfSpeech::Verb, PartOfSpeech::Adverb, PartOfSpeech::Noun]);
+    }
+}
@@ -0,0 +1,392 @@
+//! WebAssembly bindings to poi-core for the JS miners and clients: the text
+//! verifier with detailed rejection reasons, challenge and word derivation,
+//! the PoW preimage and the difficulty check. They run the same code as the
+//! program, so a text that passes here passes `submit_solution`'s checks.
+//!
+//! Build with `wasm-pack build crates/poi-wasm --target nodejs` (or `web`).
+//! u64 arguments (difficulty, nonce) are BigInt on the JS side.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use poi_core::challenge::{self, Challenge, ChallengeRejection};
+use poi_core::lang::{self, LanguagePack};
+use poi_core::mining;
+use poi_core::verify::{self, TextRejection, TextRules};
+use poi_core::words::PackedWordlist;
+use wasm_bindgen::prelude::*;
+
+/// One epoch's challenge, built from the fields of `mine_state`.
+#[wasm_bindgen]
+pub struct Epoch {
+    seed: [u8; 32],
+    difficulty: u64,
+    rules: TextRules, // scaled for `difficulty`
+    wordlist_version: u8,
+    word_list: Option<(Vec<u8>, [usize; 4])>,
+}
+
+#[wasm_bindgen]
+impl Epoch {
+    /// `rules` is Borsh `TextRules`: the 64 zero-padded bytes after the length
+    /// byte of `mine_state.text_rules`, or `default_rules()`/`published_rules()`.
+    /// Fields past the end read as 0. Empty or all-zero means `TextRules::DEFAULT`,
+    /// as in `MineState::active_text_rules`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: &[u8], difficulty: u64, rules: &[u8], wordlist_version: u8) -> Result<Epoch, JsError> {
+        let seed = to_array(seed, "seed")?;
+        let rules = parse_rules(rules).ok_or_else(|| JsError::new("invalid text rules"))?;
+        Ok(Epoch {
+            seed,
+            difficulty,
+            rules: rules.for_difficulty(difficulty),
+            wordlist_version,
+            word_list: None,
+        })
+    }
+
+    /// Derive English words from an on-chain WordList (`mine_state.word_list`):
+    /// its `count` word slots, read from WORD_LIST_HEADER, and its `groups`.
+    pub fn set_word_list(&mut self, slots: Vec<u8>, groups: &[u16]) -> Result<(), JsError> {
+        let groups: [u16; 4] = groups.try_into().map_err(|_| JsError::new("groups must have 4 entries"))?;
+        let groups = groups.map(usize::from);
+        if !PackedWordlist::new(&slots, groups).is_valid() {
+            return Err(JsError::new("invalid word list"));
+        }
+        self.word_list = Some((slots, groups));
+        Ok(())
+    }
+
+    /// ISO 639-1 code of the language this epoch's text must be written in.
+    pub fn language(&self) -> String {
+        self.pack().code.into()
+    }
+
+    /// `challenge::KIND_*` bit of this epoch's challenge.
+    pub fn challenge_kind(&self) -> u8 {
+        self.with_challenge(|_, challenge| challenge.kind())
+    }
+
+    /// What the text must do, to pass to the text generator.
+    pub fn describe(&self) -> String {
+        self.with_challenge(|_, challenge| challenge.describe())
+    }
+
+    /// Required words, in order (empty for kinds without them).
+    pub fn derive_words(&self) -> Vec<String> {
+        self.with_challenge(|_, challenge| {
+            let rw = challenge.required_words();
+            (0..rw.count)
+                .map(|i| String::from_utf8_lossy(&rw.words[i][..rw.lens[i]]).into_owned())
+                .collect()
+        })
+    }
+
+    /// Run `text` through the verifier and the challenge, as `submit_solution`
+    /// does. The near-duplicate index is on-chain state and is not checked; see
+    /// `fingerprint_shards` for the accounts it needs.
+    pub fn verify_text(&self, text: &str) -> Verdict {
+        self.with_challenge(|pack, challenge| {
+            let rw = challenge.required_words();
+            let words: Vec<&[u8]> = (0..rw.count).map(|i| &rw.words[i][..rw.lens[i]]).collect();
+            if let Err(rejection) = verify::verify_text_in(pack, text.as_bytes(), &words, &self.rules) {
+                return Verdict::text(rejection, &self.rules, text.len(), &words);
+            }
+            match challenge.verify(pack, text.as_bytes()) {
+                Ok(()) => Verdict::OK,
+                Err(rejection) => Verdict::challenge(rejection, challenge),
+            }
+        })
+    }
+
+    /// Near-duplicate index shard of `text` for each fingerprint block, in block
+    /// order: the `shard` seed of the `["fingerprints", epoch, block, shard, page]`
+    /// pages `submit_solution` takes. Empty when the index is off.
+    pub fn fingerprint_shards(&self, text: &str) -> Vec<u8> {
+        if self.rules.fingerprint_shards == 0 {
+            return Vec::new();
+        }
+        let fingerprint = verify::simhash(text.as_bytes());
+        (0..self.rules.near_duplicate_bits + 1)
+            .map(|block| verify::fingerprint_shard(fingerprint, block, &self.rules))
+            .collect()
+    }
+}
+
+impl Epoch {
+    fn pack(&self) -> &'static LanguagePack {
+        lang::select(self.rules.languages, &self.seed)
+    }
+
+    fn with_challenge<R>(&self, f: impl FnOnce(&LanguagePack, &Challenge) -> R) -> R {
+        let pack = self.pack();
+        let packed = self.word_list.as_ref().map(|(slots, groups)| PackedWordlist::new(slots, *groups));
+        let list = pack.active_wordlist(self.wordlist_version, packed.as_ref());
+        let challenge = challenge::derive(&self.rules, pack, list, &self.seed, self.difficulty);
+        f(pack, &challenge)
+    }
+}
+
+/// Outcome of `Epoch::verify_text`.
+#[wasm_bindgen(getter_with_clone)]
+pub struct Verdict {
+    pub ok: bool,
+    /// The program's error name (e.g. "TextVowelRatio"); empty when ok
+    pub reason: String,
+    /// What failed, with this epoch's thresholds
+    pub detail: String,
+    /// First required word not found in order, for TextMissingRequiredWord
+    pub word_index: Option<u32>,
+}
+
+impl Verdict {
+    const OK: Verdict = Verdict { ok: true, reason: String::new(), detail: String::new(), word_index: None };
+
+    fn rejected(reason: &str, detail: String) -> Verdict {
+        Verdict { ok: false, reason: reason.into(), detail, word_index: None }
+    }
+
+    fn text(rejection: TextRejection, r: &TextRules, len: usize, words: &[&[u8]]) -> Verdict {
+        use TextRejection::*;
+        let v2 = r.version >= verify::RULES_V2;
+        match rejection {
+            Length => Verdict::rejected(
+                "TextLength",
+                format!("Text is {} bytes; it must be {}–{}", len, r.min_len, r.max_len),
+            ),
+            NonAscii => Verdict::rejected(
+                "TextNonAscii",
+                "Text contains a character outside ASCII and the language's letters and punctuation".into(),
+            ),
+            SentenceWordCount => Verdict::rejected(
+                "TextSentenceWordCount",
+                format!("Every sentence needs {}–{} words", r.min_sentence_words, r.max_sentence_words),
+            ),
+            SentenceStructure => Verdict::rejected(
+                "TextSentenceStructure",
+                "Every sentence must start with a capital letter and end with '.', '!' or '?'".into(),
+            ),
+            DuplicateSentence => Verdict::rejected("TextDuplicateSentence", "A sentence appears twice".into()),
+            TooFewSentences => Verdict::rejected(
+                "TextTooFewSentences",
+                format!("Text needs at least {} sentences", r.min_sentences),
+            ),
+            MissingQuestion => Verdict::rejected(
+                "TextMissingQuestion",
+                format!("Text needs at least {} sentences ending in '?'", r.min_questions),
+            ),
+            MissingShortSentence => Verdict::rejected(
+                "TextMissingShortSentence",
+                format!("Text needs a sentence of at most {} words", r.short_sentence_words),
+            ),
+            MissingLongSentence => Verdict::rejected(
+                "TextMissingLongSentence",
+                format!("Text needs a sentence of at least {} words", r.long_sentence_words),
+            ),
+            VowelRatio => Verdict::rejected(
+                "TextVowelRatio",
+                format!("Vowels must be {}–{}% of letters", r.min_vowel_pct, r.max_vowel_pct),
+            ),
+            SpaceRatio => Verdict::rejected(
+                "TextSpaceRatio",
+                format!("Spaces must be {}–{}% of bytes", r.min_space_pct, r.max_space_pct),
+            ),
+            ConsonantCluster => Verdict::rejected(
+                "TextConsonantCluster",
+                format!(
+                    "Consonant runs must be at most {} letters and average under {}.{}",
+                    r.max_consonant_run,
+                    r.max_avg_cluster_x10 / 10,
+                    r.max_avg_cluster_x10 % 10
+                ),
+            ),
+            Bigram => Verdict::rejected(
+                "TextBigram",
+                if v2 {
+                    format!("Each common letter pair must appear at least {} times (and len / 80)", r.min_bigram_count)
+                } else {
+                    format!("Each common letter pair must appear at least {} times", r.min_bigram_count)
+                },
+            ),
+            ByteDiversity => Verdict::rejected(
+                "TextByteDiversity",
+                format!("Text needs at least {} distinct characters", r.min_unique_bytes),
+            ),
+            LetterFrequency => Verdict::rejected(
+                "TextLetterFrequency",
+                format!("Letter frequencies are too far from the language (chi-squared over {})", r.max_letter_chi2),
+            ),
+            TrigramFrequency => Verdict::rejected(
+                "TextTrigramFrequency",
+                format!("Under {}% of letter triples are common in the language", r.min_trigram_pct),
+            ),
+            DictionaryWords => Verdict::rejected(
+                "TextDictionaryWords",
+                format!("Under {}% of words are dictionary words", r.min_dictionary_pct),
+            ),
+            RepeatedPhrases => Verdict::rejected(
+                "TextRepeatedPhrases",
+                format!("Over {}% of three-word phrases repeat", r.max_repeated_shingle_pct),
+            ),
+            MissingRequiredWord(idx) => {
+                let word = words.get(idx).map(|w| String::from_utf8_lossy(w).into_owned()).unwrap_or_default();
+                Verdict {
+                    word_index: Some(idx as u32),
+                    ..Verdict::rejected(
+                        "TextMissingRequiredWord",
+                        format!(
+                            "Required word {} (\"{}\") not found in order, at least {} bytes after the previous one",
+                            idx, word, r.min_word_gap
+                        ),
+                    )
+                }
+            }
+        }
+    }
+
+    fn challenge(rejection: ChallengeRejection, challenge: &Challenge) -> Verdict {
+        let reason = match rejection {
+            ChallengeRejection::Acrostic => "ChallengeAcrostic",
+            ChallengeRejection::SentenceStart => "ChallengeSentenceStart",
+            ChallengeRejection::QuestionWord => "ChallengeQuestionWord",
+            ChallengeRejection::SentenceParity => "ChallengeSentenceParity",
+            ChallengeRejection::Category => "ChallengeCategory",
+        };
+        Verdict::rejected(reason, challenge.describe())
+    }
+}
+
+/// Borsh bytes of `TextRules::DEFAULT`.
+#[wasm_bindgen]
+pub fn default_rules() -> Vec<u8> {
+    TextRules::DEFAULT.try_to_vec().unwrap()
+}
+
+/// Borsh bytes of `TextRules::PUBLISHED`.
+#[wasm_bindgen]
+pub fn published_rules() -> Vec<u8> {
+    TextRules::PUBLISHED.try_to_vec().unwrap()
+}
+
+/// PoW preimage: challenge_seed ‖ miner ‖ text ‖ "||" ‖ nonce (u64 LE).
+#[wasm_bindgen]
+pub fn pow_preimage(seed: &[u8], miner: &[u8], text: &str, nonce: u64) -> Result<Vec<u8>, JsError> {
+    Ok(mining::pow_preimage(&to_array(seed, "seed")?, &to_array(miner, "miner")?, text.as_bytes(), nonce))
+}
+
+/// keccak256 of `pow_preimage`.
+#[wasm_bindgen]
+pub fn pow_hash(seed: &[u8], miner: &[u8], text: &str, nonce: u64) -> Result<Vec<u8>, JsError> {
+    Ok(mining::pow_hash(&to_array(seed, "seed")?, &to_array(miner, "miner")?, text.as_bytes(), nonce).to_vec())
+}
+
+/// True if `hash` has at least `difficulty` leading zero bits.
+#[wasm_bindgen]
+pub fn check_difficulty(hash: &[u8], difficulty: u64) -> Result<bool, JsError> {
+    Ok(mining::check_difficulty(&to_array(hash, "hash")?, difficulty))
+}
+
+/// Text rules from Borsh bytes, zero-padded either way to `TextRules::SIZE`;
+/// empty or all-zero is the default. Non-zero bytes past SIZE are a newer
+/// layout than this build.
+fn parse_rules(bytes: &[u8]) -> Option<TextRules> {
+    if bytes.iter().all(|&b| b == 0) {
+        return Some(TextRules::DEFAULT);
+    }
+    let n = bytes.len().min(TextRules::SIZE);
+    if bytes[n..].iter().any(|&b| b != 0) {
+        return None;
+    }
+    let mut padded = [0u8; TextRules::SIZE];
+    padded[..n].copy_from_slice(&bytes[..n]);
+    TextRules::try_from_slice(&padded).ok().filter(TextRules::is_valid)
+}
+
+fn to_array(bytes: &[u8], name: &str) -> Result<[u8; 32], JsError> {
+    bytes.try_into().map_err(|_| JsError::new(&format!("{name} must be 32 bytes")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poi_core::words;
+
+    /// First cu_corpus text with `{0}` `{1}` `{2}` filled in.
+    fn corpus_text(words: &[String]) -> String {
+        let doc: serde_json::Value =
+            serde_json::from_str(include_str!("../../../tests/vectors/cu_corpus.json")).unwrap();
+        doc["texts"][0]["text"].as_str().unwrap()
+            .replace("{0}", &words[0]).replace("{1}", &words[1]).replace("{2}", &words[2])
+    }
+
+    #[test]
+    fn test_epoch_matches_core() {
+        let seed = [42u8; 32];
+        let epoch = Epoch::new(&seed, 8, &published_rules(), 0).unwrap();
+        assert_eq!(epoch.language(), "en");
+        assert_eq!(epoch.challenge_kind(), challenge::KIND_REQUIRED_WORDS);
+
+        let rw = words::derive_words(&seed, 8);
+        let expected: Vec<String> = (0..rw.count)
+            .map(|i| String::from_utf8(rw.words[i][..rw.lens[i]].to_vec()).unwrap())
+            .collect();
+        assert_eq!(epoch.derive_words(), expected);
+
+        // Zeroed rules (a pre-rules account) are the default
+        assert_eq!(parse_rules(&[0; TextRules::SIZE]), Some(TextRules::DEFAULT));
+        assert_eq!(parse_rules(&default_rules()), Some(TextRules::DEFAULT));
+        assert_eq!(default_rules().len(), TextRules::SIZE);
+        let invalid = TextRules { min_len: 900, max_len: 800, ..TextRules::DEFAULT };
+        assert_eq!(parse_rules(&invalid.try_to_vec().unwrap()), None);
+
+        // As padded in mine_state: trailing zeros are ignored, anything else is a newer layout
+        let mut padded = published_rules();
+        padded.resize(64, 0);
+        assert_eq!(parse_rules(&padded), Some(TextRules::PUBLISHED));
+        padded[63] = 1;
+        assert_eq!(parse_rules(&padded), None);
+    }
+
+    #[test]
+    fn test_verify_text() {
+        let epoch = Epoch::new(&[42u8; 32], 8, &published_rules(), 0).unwrap();
+        let words = epoch.derive_words();
+        let verdict = epoch.verify_text(&corpus_text(&words));
+        assert!(verdict.ok, "{}: {}", verdict.reason, verdict.detail);
+
+        // Words out of order: the second one is the first missing
+        let swapped = [words[1].clone(), words[0].clone(), words[2].clone()];
+        let verdict = epoch.verify_text(&corpus_text(&swapped));
+        assert!(!verdict.ok);
+        assert_eq!(verdict.reason, "TextMissingRequiredWord");
+        assert_eq!(verdict.word_index, Some(1));
+        assert!(verdict.detail.contains(&words[1]));
+
+        let verdict = epoch.verify_text("Too short?");
+        assert_eq!(verdict.reason, "TextLength");
+        assert_eq!(verdict.detail, "Text is 10 bytes; it must be 256–800");
+    }
+
+    #[test]
+    fn test_fingerprint_shards() {
+        let text = "The quick brown fox jumps over the lazy dog.";
+        let off = Epoch::new(&[42u8; 32], 8, &default_rules(), 0).unwrap();
+        assert!(off.fingerprint_shards(text).is_empty());
+
+        let rules = TextRules { fingerprint_shards: 10, near_duplicate_bits: 3, ..TextRules::DEFAULT };
+        let epoch = Epoch::new(&[42u8; 32], 8, &rules.try_to_vec().unwrap(), 0).unwrap();
+        let fingerprint = verify::simhash(text.as_bytes());
+        let expected: Vec<u8> = (0..4).map(|block| verify::fingerprint_shard(fingerprint, block, &rules)).collect();
+        assert_eq!(epoch.fingerprint_shards(text), expected);
+        assert!(expected.iter().all(|&shard| shard < 10));
+    }
+
+    #[test]
+    fn test_pow() {
+        let (seed, miner) = ([1u8; 32], [2u8; 32]);
+        let preimage = pow_preimage(&seed, &miner, "text", 5).unwrap();
+        assert_eq!(preimage, mining::pow_preimage(&seed, &miner, b"text", 5));
+        let hash = pow_hash(&seed, &miner, "text", 5).unwrap();
+        assert_eq!(hash, mining::pow_hash(&seed, &miner, b"text", 5));
+        assert!(check_difficulty(&[0; 32], 256).unwrap());
+        assert!(!check_difficulty(&[0xFF; 32], 1).unwrap());
+    }
+}
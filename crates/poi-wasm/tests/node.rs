@@ -0,0 +1,40 @@
+//! Runs the bindings through the JS boundary under Node:
+//! `wasm-pack test --node crates/poi-wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use poi_wasm::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn rejects_bad_input() {
+    assert!(Epoch::new(&[0; 31], 8, &[], 0).is_err());
+    assert!(Epoch::new(&[0; 32], 8, &[1, 2, 3], 0).is_err());
+    assert!(pow_hash(&[0; 32], &[0; 16], "text", 0).is_err());
+    assert!(check_difficulty(&[0; 8], 1).is_err());
+
+    let mut epoch = Epoch::new(&[0; 32], 8, &[], 0).unwrap();
+    assert!(epoch.set_word_list(vec![0; 16], &[1, 1, 0, 0]).is_err());
+    assert!(epoch.set_word_list(vec![0; 16], &[2]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn verifies_text() {
+    let epoch = Epoch::new(&[42; 32], 8, &published_rules(), 0).unwrap();
+    let words = epoch.derive_words();
+    assert_eq!(words.len(), 3);
+    let verdict = epoch.verify_text("Too short?");
+    assert!(!verdict.ok);
+    assert_eq!(verdict.reason, "TextLength");
+    // The published rules keep the near-duplicate index off
+    assert!(epoch.fingerprint_shards("Too short?").is_empty());
+}
+
+#[wasm_bindgen_test]
+fn grinds_a_nonce() {
+    let (seed, miner) = ([3; 32], [4; 32]);
+    let nonce = (0u64..).find(|&n| check_difficulty(&pow_hash(&seed, &miner, "text", n).unwrap(), 8).unwrap()).unwrap();
+    let hash = pow_hash(&seed, &miner, "text", nonce).unwrap();
+    assert_eq!(hash[0], 0);
+    assert_eq!(pow_preimage(&seed, &miner, "text", nonce).unwrap().len(), 32 + 32 + 4 + 2 + 8);
+}
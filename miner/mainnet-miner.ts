@@ -13,6 +13,8 @@ import {
 } from "@solana/spl-token";
 import { createHash } from "crypto";
 import * as fs from "fs";
+// poi-core compiled to WebAssembly: build with `npm run wasm` in the repo root
+import { Epoch, pow_hash, check_difficulty } from "../crates/poi-wasm/pkg";
 
 // ── Config ──
 const RPC_URL = process.env.RPC_URL || "https://solana-rpc.publicnode.com";
@@ -38,60 +40,68 @@ function disc(name: string) {
   return createHash("sha256").update("global:" + name).digest().subarray(0, 8);
 }
 
-// ── Word list (exact match with contract words.rs — 200 words) ──
-const WORDLIST: string[] = [
-  "time","life","world","place","water","light","house","music","power","dream",
-  "heart","earth","ocean","river","cloud","stone","flame","voice","night","field",
-  "space","brain","truth","peace","storm","tower","plant","metal","glass","wheel",
-  "bridge","forest","garden","market","island","desert","silver","shadow","spirit","nature",
-  "energy","future","memory","moment","season","winter","summer","signal","system","design",
-  "method","reason","answer","letter","person","animal","flower","morning","evening","journey",
-  "history","culture","balance","freedom","pattern","shelter","surface","chapter","element","silence",
-  "think","learn","build","write","speak","dance","climb","watch","shine","carry",
-  "drive","paint","teach","reach","solve","share","trust","guide","shape","craft",
-  "chase","drift","weave","bloom","grasp","shift","sweep","trace","wander","gather",
-  "create","follow","listen","notice","wonder","happen","become","remain","travel","return",
-  "search","reveal","explore","imagine","connect","protect","reflect","develop","consider","discover",
-  "bright","quiet","gentle","strong","simple","hidden","golden","silent","frozen","bitter",
-  "tender","vivid","subtle","fierce","humble","steady","clever","honest","broken","sacred",
-  "unique","global","active","native","smooth","narrow","liquid","mental","social","visual",
-  "formal","casual","proper","remote","secure","stable","cosmic","ancient","modern","natural",
-  "digital","central","special","private","perfect","strange","careful","curious","distant","endless",
-  "often","never","always","slowly","deeply","gently","simply","nearly","barely","mostly",
-  "partly","surely","truly","fully","quite","still","maybe","hence","twice","ahead",
-  "apart","aside","along","after","again","early","later","since","almost","around",
-];
-
-// ── Derive required words from challenge_seed (matches contract logic) ──
-function wordCountForDifficulty(difficulty: number): number {
-  if (difficulty <= 10) return 3;
-  if (difficulty <= 15) return 4;
-  if (difficulty <= 20) return 5;
-  if (difficulty <= 30) return 6;
-  if (difficulty <= 40) return 7;
-  return 8;
+// ── Active on-chain WordList (mine_state.word_list), cached by address ──
+// Layout (after 8-byte discriminator):
+//  8: authority (Pubkey, 32)
+// 40: hash ([u8;32])
+// 72: id (u64)
+// 80: groups ([u16;4])
+// 88: count (u16)
+// 90: sealed (u8)
+// 91: bump (u8)
+// 96: words, zero-padded 8-byte slots
+const WORD_SLOT = 8;
+const wordListCache = new Map<string, { slots: Buffer; groups: Uint16Array }>();
+
+async function fetchWordList(addr: PublicKey) {
+  const cached = wordListCache.get(addr.toBase58());
+  if (cached) return cached;
+  const info = await conn.getAccountInfo(addr);
+  if (!info) throw new Error(`word list ${addr.toBase58()} not found`);
+  const d = info.data;
+  if (d[90] !== 1) throw new Error(`word list ${addr.toBase58()} is not sealed`);
+  const count = d.readUInt16LE(88);
+  const list = {
+    slots: Buffer.from(d.subarray(96, 96 + count * WORD_SLOT)),
+    groups: Uint16Array.from([0, 1, 2, 3], i => d.readUInt16LE(80 + i * 2)),
+  };
+  wordListCache.set(addr.toBase58(), list);
+  return list;
 }
 
-function deriveWords(seed: Buffer, difficulty: number): string[] {
-  const count = wordCountForDifficulty(difficulty);
-  const used = new Set<number>();
-  const words: string[] = [];
-
-  for (let i = 0; i < count; i++) {
-    const raw = (seed[i * 2]! << 8) | seed[i * 2 + 1]!;
-    let idx = raw % WORDLIST.length;
-
-    let tries = 0;
-    while (used.has(idx) && tries < WORDLIST.length) {
-      idx = (idx + 1) % WORDLIST.length;
-      tries++;
+// ── Near-duplicate index pages (FingerprintShard), when TextRules turn it on ──
+// Layout (after 8-byte discriminator):
+//  8: epoch (u64)
+// 16: block (u8)
+// 17: shard (u8)
+// 18: page (u8)
+// 19: payer (Pubkey, 32)
+// 51: bump (u8)
+// 52: fingerprints (Vec<u64>: u32 length, then up to 128)
+const FINGERPRINTS_PER_PAGE = 128;
+
+// Each block's shard pages up to the first one not full, in block order. A missing
+// page is created by submit_solution at the miner's expense. Only that last page
+// per block is written, so full pages go in read-only.
+async function fingerprintPages(epoch: Epoch, epochNumber: number, text: string) {
+  const epochBuf = Buffer.alloc(8);
+  epochBuf.writeBigUInt64LE(BigInt(epochNumber));
+  const shards = epoch.fingerprint_shards(text);
+  const pages: { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[] = [];
+  for (let block = 0; block < shards.length; block++) {
+    for (let page = 0; ; page++) {
+      if (page > 255) throw new Error(`near-duplicate shard ${shards[block]} of block ${block} has no pages left`);
+      const [addr] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fingerprints"), epochBuf, Buffer.from([block]), Buffer.from([shards[block]!]), Buffer.from([page])],
+        PROGRAM_ID
+      );
+      const info = await conn.getAccountInfo(addr);
+      const full = info !== null && info.data.readUInt32LE(52) >= FINGERPRINTS_PER_PAGE;
+      pages.push({ pubkey: addr, isSigner: false, isWritable: !full });
+      if (!full) break;
     }
-    if (tries >= WORDLIST.length) break;
-
-    used.add(idx);
-    words.push(WORDLIST[idx]!);
   }
-  return words;
+  return pages;
 }
 
 // ── Text generation (meets verify.rs: 256-800 bytes, words in order, ≥40 byte gap, sentences, etc.) ──
@@ -134,39 +144,30 @@ function generateText(words: string[]): string {
   return parts.join(" ");
 }
 
-// ── PoW grinding (Keccak-256, matches contract exactly) ──
+// ── PoW grinding (Keccak-256 via poi-wasm, the program's own code) ──
 // Hash = keccak256(challenge_seed | miner_key | text | "||" | nonce_le)
 // Difficulty = number of leading zero BITS required
-function checkDifficulty(hash: Buffer, difficulty: number): boolean {
-  const fullBytes = Math.floor(difficulty / 8);
-  const remainingBits = difficulty % 8;
-  for (let i = 0; i < fullBytes; i++) {
-    if (hash[i] !== 0) return false;
-  }
-  if (remainingBits > 0 && fullBytes < 32) {
-    const mask = 0xFF << (8 - remainingBits);
-    if ((hash[fullBytes]! & mask) !== 0) return false;
+function grindNonce(
+  challengeSeed: Buffer, minerKey: PublicKey, text: string, difficulty: number
+): { nonce: bigint; hash: Uint8Array } {
+  const key = minerKey.toBytes();
+  const target = BigInt(difficulty);
+  for (let nonce = BigInt(0); ; nonce++) {
+    const hash = pow_hash(challengeSeed, key, text, nonce);
+    if (check_difficulty(hash, target)) return { nonce, hash };
   }
-  return true;
 }
 
-function grindNonce(
-  challengeSeed: Buffer, minerKey: PublicKey, text: string, difficulty: number
-): { nonce: bigint; hash: Buffer } {
-  const { keccak256 } = require("js-sha3");
-  const textBuf = Buffer.from(text, "utf-8");
-  const separator = Buffer.from("||");
-  const nonceBuf = Buffer.alloc(8);
+// ── This epoch's challenge, derived by poi-wasm exactly as the program does ──
+const KIND_REQUIRED_WORDS = 1 << 0; // challenge.rs KIND_*
 
-  let nonce = BigInt(0);
-  while (true) {
-    nonceBuf.writeBigUInt64LE(nonce);
-    const input = Buffer.concat([challengeSeed, minerKey.toBuffer(), textBuf, separator, nonceBuf]);
-    const hashHex = keccak256(input);
-    const hash = Buffer.from(hashHex, "hex");
-    if (checkDifficulty(hash, difficulty)) return { nonce, hash };
-    nonce++;
+async function loadEpoch(state: Awaited<ReturnType<typeof readMineState>>): Promise<Epoch> {
+  const epoch = new Epoch(state.challengeSeed, BigInt(state.difficulty), state.textRules, state.wordlistVersion);
+  if (!state.wordList.equals(PublicKey.default)) {
+    const { slots, groups } = await fetchWordList(state.wordList);
+    epoch.set_word_list(slots, groups);
   }
+  return epoch;
 }
 
 // ── Read mine_state ──
@@ -187,6 +188,11 @@ async function readMineState() {
   // 104: mint (Pubkey, 32)
   // 136: crank_authority (Pubkey, 32)
   // 168: bump (u8)
+  // 169: pending_authority (Pubkey, 32)
+  // 201: paused (u8, PAUSE_* bitmask)
+  // 202: text_rules (len u8, then Borsh TextRules zero-padded to 64; len 0 = defaults)
+  // 267: wordlist_version (u8, 0/1 = V1)
+  // 268: word_list (Pubkey, 32; default = built-in list)
   return {
     totalMined: d.readBigUInt64LE(8),
     difficulty: Number(d.readBigUInt64LE(16)),
@@ -196,6 +202,10 @@ async function readMineState() {
     epochEnd: Number(d.readBigInt64LE(72)),
     solutionsInEpoch: Number(d.readBigUInt64LE(80)),
     totalSupply: d.readBigUInt64LE(96),
+    paused: d.length > 201 ? d[201]! : 0,
+    textRules: d.length >= 267 ? Buffer.from(d.subarray(203, 267)) : Buffer.alloc(0), // empty = defaults
+    wordlistVersion: d.length > 267 ? d[267]! : 0,
+    wordList: d.length >= 300 ? new PublicKey(d.subarray(268, 300)) : PublicKey.default,
   };
 }
 
@@ -204,7 +214,10 @@ let localSolutionCount = 0;
 
 // ── Submit solution ──
 // Anchor args order: text (String), nonce (u64)
-async function submitSolution(epoch: number, nonce: bigint, text: string) {
+async function submitSolution(
+  epoch: number, nonce: bigint, text: string, wordList: PublicKey,
+  pages: Awaited<ReturnType<typeof fingerprintPages>>,
+) {
   const textBuf = Buffer.from(text, "utf-8");
   const [solnAddr] = PublicKey.findProgramAddressSync(
     [Buffer.from("solution"), miner.publicKey.toBuffer(), new Uint8Array(new BigUint64Array([BigInt(epoch)]).buffer)],
@@ -229,6 +242,9 @@ async function submitSolution(epoch: number, nonce: bigint, text: string) {
       { pubkey: solnAddr, isSigner: false, isWritable: true },
       { pubkey: miner.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      // Remaining accounts: the active WordList, if any, then the near-duplicate index pages
+      ...(wordList.equals(PublicKey.default) ? [] : [{ pubkey: wordList, isSigner: false, isWritable: false }]),
+      ...pages,
     ],
     data,
   }));
@@ -358,7 +374,7 @@ async function main() {
       const now = Math.floor(Date.now() / 1000);
       const remaining = state.epochEnd - now;
 
-      console.log(`[${new Date().toISOString()}] Epoch ${state.epoch} | Difficulty ${state.difficulty} | Ends in ${remaining}s`);
+      console.log(`[${new Date().toISOString()}] Epoch ${state.epoch} | Difficulty ${state.difficulty} | Ends in ${remaining}s${state.paused ? ` | Paused 0x${state.paused.toString(16)}` : ""}`);
 
       // Epoch ended → advance + claim
       if (remaining <= 0) {
@@ -387,6 +403,19 @@ async function main() {
         continue;
       }
 
+      // The text generator writes English sentences around required words
+      const epoch = await loadEpoch(state);
+      if (epoch.language() !== "en") {
+        console.log(`  Language "${epoch.language()}" is not supported by this miner (English only), waiting...`);
+        await new Promise(r => setTimeout(r, Math.min(remaining, 30) * 1000));
+        continue;
+      }
+      if (epoch.challenge_kind() !== KIND_REQUIRED_WORDS) {
+        console.log(`  Challenge not supported by this miner (required words only): ${epoch.describe()}, waiting...`);
+        await new Promise(r => setTimeout(r, Math.min(remaining, 30) * 1000));
+        continue;
+      }
+
       // Already submitted this epoch
       if (lastSubmittedEpoch === state.epoch) {
         const wait = Math.min(remaining, 30);
@@ -396,9 +425,15 @@ async function main() {
       }
 
       // Mine!
-      const words = deriveWords(state.challengeSeed, state.difficulty);
+      const words = epoch.derive_words();
       const text = generateText(words);
       console.log(`  Required words (${words.length}): ${words.join(", ")}`);
+      const verdict = epoch.verify_text(text);
+      if (!verdict.ok) {
+        console.log(`  ⚠️ Generated text would be rejected (${verdict.reason}): ${verdict.detail}`);
+        await new Promise(r => setTimeout(r, Math.min(remaining, 30) * 1000));
+        continue;
+      }
       console.log(`  Grinding nonce (difficulty=${state.difficulty})...`);
 
       const t0 = Date.now();
@@ -407,7 +442,8 @@ async function main() {
       console.log(`  Found nonce ${nonce} in ${elapsed}s`);
 
       console.log("  Submitting solution...");
-      const sig = await submitSolution(state.epoch, nonce, text);
+      const pages = await fingerprintPages(epoch, state.epoch, text);
+      const sig = await submitSolution(state.epoch, nonce, text, state.wordList, pages);
       console.log(`  ✅ Submitted: ${sig}`);
       lastSubmittedEpoch = state.epoch;
       localSolutionCount++;
@@ -7,7 +7,6 @@ import {
   TransactionInstruction, sendAndConfirmTransaction,
   ComputeBudgetProgram, SystemProgram,
 } from '@solana/web3.js';
-import { keccak_256 } from '@noble/hashes/sha3';
 import { sha256 } from '@noble/hashes/sha256';
 import {
   TOKEN_PROGRAM_ID,
@@ -15,6 +14,8 @@ import {
   createAssociatedTokenAccountIdempotentInstruction,
 } from '@solana/spl-token';
 import * as fs from 'fs';
+// poi-core compiled to WebAssembly: build with `npm run wasm` in the repo root
+import { Epoch, pow_hash, check_difficulty } from '../crates/poi-wasm/pkg';
 
 const RPC = 'http://127.0.0.1:8899';
 const PROGRAM = new PublicKey('Aio7qosxjY32JuFfSrbpdv2kqYu3MF6YynPdai22HMAg');
@@ -28,19 +29,50 @@ function disc(name: string): Buffer {
 const [stateAddr] = PublicKey.findProgramAddressSync([Buffer.from('mine_state')], PROGRAM);
 const [mintAddr] = PublicKey.findProgramAddressSync([Buffer.from('mint')], PROGRAM);
 
-// ── Word list & derivation ──
-const WORDS = ["time","life","world","place","water","light","house","music","power","dream","heart","earth","ocean","river","cloud","stone","flame","voice","night","field","space","brain","truth","peace","storm","tower","plant","metal","glass","wheel","bridge","forest","garden","market","island","desert","silver","shadow","spirit","nature","energy","future","memory","moment","season","winter","summer","signal","system","design","method","reason","answer","letter","person","animal","flower","morning","evening","journey","history","culture","balance","freedom","pattern","shelter","surface","chapter","element","silence","think","learn","build","write","speak","dance","climb","watch","shine","carry","drive","paint","teach","reach","solve","share","trust","guide","shape","craft","chase","drift","weave","bloom","grasp","shift","sweep","trace","wander","gather","create","follow","listen","notice","wonder","happen","become","remain","travel","return","search","reveal","explore","imagine","connect","protect","reflect","develop","consider","discover","bright","quiet","gentle","strong","simple","hidden","golden","silent","frozen","bitter","tender","vivid","subtle","fierce","humble","steady","clever","honest","broken","sacred","unique","global","active","native","smooth","narrow","liquid","mental","social","visual","formal","casual","proper","remote","secure","stable","cosmic","ancient","modern","natural","digital","central","special","private","perfect","strange","careful","curious","distant","endless","often","never","always","slowly","deeply","gently","simply","nearly","barely","mostly","partly","surely","truly","fully","quite","still","maybe","hence","twice","ahead","apart","aside","along","after","again","early","later","since","almost","around"];
+// Active on-chain WordList (mine_state.word_list on newer layouts): groups [u16;4] at 80,
+// count u16 at 88, sealed u8 at 90, then zero-padded 8-byte word slots from byte 96
+async function fetchWordList(addr: PublicKey) {
+  const d = (await conn.getAccountInfo(addr))!.data;
+  if (d[90] !== 1) throw new Error(`word list ${addr.toBase58()} is not sealed`);
+  return {
+    slots: d.subarray(96, 96 + d.readUInt16LE(88) * 8),
+    groups: Uint16Array.from([0, 1, 2, 3], i => d.readUInt16LE(80 + i * 2)),
+  };
+}
+
+// This epoch's challenge, derived by poi-wasm exactly as the program does
+const KIND_REQUIRED_WORDS = 1 << 0; // challenge.rs KIND_*
+async function loadEpoch(s: Awaited<ReturnType<typeof readState>>): Promise<Epoch> {
+  const epoch = new Epoch(s.seed, BigInt(s.difficulty), s.textRules, s.wordlistVersion);
+  if (!s.wordList.equals(PublicKey.default)) {
+    const { slots, groups } = await fetchWordList(s.wordList);
+    epoch.set_word_list(slots, groups);
+  }
+  return epoch;
+}
 
-function wordCount(diff: number) { return diff<=10?3:diff<=15?4:diff<=20?5:diff<=30?6:diff<=40?7:8; }
-function deriveWords(seed: Uint8Array, diff: number): string[] {
-  const count = wordCount(diff);
-  const used = new Set<number>(); const result: string[] = [];
-  for (let i = 0; i < count; i++) {
-    let idx = ((seed[i*2]<<8)|seed[i*2+1]) % WORDS.length;
-    while (used.has(idx)) idx = (idx+1) % WORDS.length;
-    used.add(idx); result.push(WORDS[idx]);
+// Near-duplicate index pages (FingerprintShard), when TextRules turn it on: epoch u64 at 8,
+// block/shard/page u8 at 16/17/18, then the fingerprint count (u32) at 52, 128 per page.
+// Each block's shard pages up to the first one not full, in block order; submit_solution
+// creates a missing page at the miner's expense and only writes that last one.
+async function fingerprintPages(epoch: Epoch, epochNumber: bigint, text: string) {
+  const epochBuf = Buffer.alloc(8); epochBuf.writeBigUInt64LE(epochNumber);
+  const shards = epoch.fingerprint_shards(text);
+  const pages: { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[] = [];
+  for (let block = 0; block < shards.length; block++) {
+    for (let page = 0; ; page++) {
+      if (page > 255) throw new Error(`near-duplicate shard ${shards[block]} of block ${block} has no pages left`);
+      const [addr] = PublicKey.findProgramAddressSync(
+        [Buffer.from('fingerprints'), epochBuf, Buffer.from([block]), Buffer.from([shards[block]!]), Buffer.from([page])],
+        PROGRAM
+      );
+      const info = await conn.getAccountInfo(addr);
+      const full = info !== null && info.data.readUInt32LE(52) >= 128;
+      pages.push({ pubkey: addr, isSigner: false, isWritable: !full });
+      if (!full) break;
+    }
   }
-  return result;
+  return pages;
 }
 
 // ── Text generation (satisfies all verify.rs constraints) ──
@@ -82,27 +114,17 @@ function generateText(words: string[]): string {
   return text;
 }
 
-// ── PoW ──
-function meetsTarget(hash: Uint8Array, diff: number): boolean {
-  for (let i = 0; i < diff; i++) { if ((hash[i>>3]>>(7-(i&7)))&1) return false; }
-  return true;
-}
+// ── PoW (keccak256 and the difficulty check via poi-wasm) ──
 function grindNonce(seed: Uint8Array, miner: Uint8Array, text: string, diff: number): bigint|null {
-  const tb = new TextEncoder().encode(text);
-  const sep = new TextEncoder().encode('||');
-  const pLen = 32+32+tb.length+2;
-  const buf = new Uint8Array(pLen+8);
-  buf.set(seed,0); buf.set(miner,32); buf.set(tb,64); buf.set(sep,64+tb.length);
-  const dv = new DataView(buf.buffer, pLen, 8);
+  const target = BigInt(diff);
   for (let n=0n; n<50_000_000n; n++) {
-    dv.setBigUint64(0,n,true);
-    if (meetsTarget(keccak_256(buf),diff)) return n;
+    if (check_difficulty(pow_hash(seed, miner, text, n), target)) return n;
     if (n % 1_000_000n === 0n && n > 0n) process.stdout.write(`  ${n/1_000_000n}M...`);
   }
   return null;
 }
 
-// ── Read state (v2.2 layout: 169 bytes) ──
+// ── Read state (v2.2 layout: 169 bytes; later fields read when present) ──
 async function readState() {
   const info = await conn.getAccountInfo(stateAddr);
   const d = info!.data;
@@ -117,6 +139,11 @@ async function readState() {
     mint: new PublicKey(d.slice(104, 136)),
     crankAuthority: new PublicKey(d.slice(136, 168)),
     bump: d[168],
+    paused: d.length > 201 ? d[201] : 0,
+    // text_rules: len u8 at 202, then Borsh zero-padded to 64 bytes (empty = defaults)
+    textRules: d.length >= 267 ? new Uint8Array(d.slice(203, 267)) : new Uint8Array(0),
+    wordlistVersion: d.length > 267 ? d[267] : 0,
+    wordList: d.length >= 300 ? new PublicKey(d.slice(268, 300)) : PublicKey.default,
   };
 }
 async function getBlockTime() {
@@ -140,7 +167,10 @@ async function advanceEpoch(solutionCount: number) {
   return sendAndConfirmTransaction(conn, tx, [wallet], { commitment: 'confirmed' });
 }
 
-async function submitSolution(text: string, nonce: bigint, epoch: bigint) {
+async function submitSolution(
+  text: string, nonce: bigint, epoch: bigint, wordList: PublicKey,
+  pages: Awaited<ReturnType<typeof fingerprintPages>>,
+) {
   const epochBuf = Buffer.alloc(8); epochBuf.writeBigUInt64LE(epoch);
   const [solAddr] = PublicKey.findProgramAddressSync(
     [Buffer.from('solution'), wallet.publicKey.toBuffer(), epochBuf], PROGRAM
@@ -158,6 +188,8 @@ async function submitSolution(text: string, nonce: bigint, epoch: bigint) {
       { pubkey: solAddr, isSigner: false, isWritable: true },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...(wordList.equals(PublicKey.default) ? [] : [{ pubkey: wordList, isSigner: false, isWritable: false }]),
+      ...pages, // near-duplicate index, after the WordList
     ],
     data,
   }));
@@ -211,10 +243,20 @@ async function main() {
 
   // Step 2: Submit solution
   console.log('\n━━━ Step 2: Submit solution ━━━');
-  const words = deriveWords(s.seed, s.difficulty);
+  // The text generator writes English sentences around required words
+  const epoch = await loadEpoch(s);
+  if (epoch.language() !== 'en') {
+    console.log(`  ❌ Language "${epoch.language()}" is not supported by this miner (English only)`); return;
+  }
+  if (epoch.challenge_kind() !== KIND_REQUIRED_WORDS) {
+    console.log(`  ❌ Challenge not supported by this miner (required words only): ${epoch.describe()}`); return;
+  }
+  const words = epoch.derive_words();
   console.log(`  Words (${words.length}): ${words.join(', ')}`);
   const text = generateText(words);
   console.log(`  Text: ${Buffer.from(text).length} bytes`);
+  const verdict = epoch.verify_text(text);
+  if (!verdict.ok) { console.log(`  ❌ Text would be rejected (${verdict.reason}): ${verdict.detail}`); return; }
 
   console.log(`  ⛏ Grinding nonce (difficulty=${s.difficulty})...`);
   const t0 = Date.now();
@@ -223,7 +265,8 @@ async function main() {
   if (nonce === null) { console.log('\n  ❌ No nonce found in 50M tries'); return; }
   console.log(`\n  ⛏ Nonce: ${nonce} (${elapsed}s)`);
 
-  const submitSig = await submitSolution(text, nonce, s.epoch);
+  const pages = await fingerprintPages(epoch, s.epoch, text);
+  const submitSig = await submitSolution(text, nonce, s.epoch, s.wordList, pages);
   console.log(`  ✅ Submitted: ${submitSig}`);
 
   // Step 3: Wait for epoch to end
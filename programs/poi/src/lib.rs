@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
 use anchor_lang::solana_program::keccak;
+use anchor_lang::system_program;
 use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use std::cell::Ref;
 
-pub mod verify;
-pub mod words;
+pub use poi_core::{challenge, dictionary, lang, verify, words};
+use poi_core::mining::{
+    calculate_reward, check_difficulty, pow_hash, vested_amount, MAX_SUPPLY, VESTING_DURATION,
+};
 
 declare_id!("AcTXBfHAJgwt1sTn3DvTSKiiCKgShzGEZzq2zQrs5BnG");
 
@@ -11,9 +16,6 @@ declare_id!("AcTXBfHAJgwt1sTn3DvTSKiiCKgShzGEZzq2zQrs5BnG");
 // Constants
 // ============================================================
 
-const MAX_SUPPLY: u64 = 100_000_000_000_000;               // 100B × 10^3 (3 decimals)
-const INITIAL_REWARD: u64 = 25_000_000;                    // 25K CRB × 10^3
-const HALVING_INTERVAL: u64 = 2_000_000;
 const EPOCH_DURATION: i64 = 600;                            // 10 min
 const TARGET_SOLUTIONS: u64 = 50;
 const INITIAL_DIFFICULTY: u64 = 8;
@@ -21,7 +23,20 @@ const MAX_DIFFICULTY: u64 = 250;
 const MIN_DIFFICULTY: u64 = 4;
 const MAX_DIFFICULTY_ADJ: u64 = 5;
 const CLAIM_EXPIRY_EPOCHS: u64 = 500;
-const VESTING_DURATION: i64 = 30 * 24 * 3600;              // 30 days in seconds
+const MAX_ADMINS: usize = 10;                               // fits the u16 approval bitmask
+const FINGERPRINTS_PER_SHARD: usize = 128;                  // per page; a full page chains to the next
+const WORD_LIST_CAPACITY: usize = 4096;                     // words a WordList account can hold
+const WORD_LIST_HEADER: usize = 8 + core::mem::size_of::<WordList>(); // word slots start here
+const TEXT_RULES_PADDED_LEN: usize = 64;                    // room for TextRules to grow in place
+const _: () = assert!(verify::TextRules::SIZE <= TEXT_RULES_PADDED_LEN);
+
+// Pause bits for MineState.paused (set via `set_pause`)
+pub const PAUSE_SUBMIT: u8 = 1 << 0;
+pub const PAUSE_CLAIM: u8 = 1 << 1;
+pub const PAUSE_WITHDRAW_UNLOCKED: u8 = 1 << 2;             // blocks withdraw, including already-unlocked balances
+pub const PAUSE_ADVANCE: u8 = 1 << 3;
+pub const PAUSE_CLOSE: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 = 0b0001_1111;
 
 // ============================================================
 // Program
@@ -59,6 +74,11 @@ pub mod proof_of_inference {
         state.mint = mint_key;
         state.crank_authority = ctx.accounts.payer.key();
         state.bump = bump;
+        state.pending_authority = Pubkey::default();
+        state.paused = 0;
+        state.text_rules = PaddedTextRules::new(&verify::TextRules::DEFAULT);
+        state.wordlist_version = words::WORDLIST_V1;
+        state.word_list = Pubkey::default();
 
         Ok(())
     }
@@ -66,8 +86,18 @@ pub mod proof_of_inference {
     /// Submit a mining solution.
     ///
     /// mine_state is READ-ONLY — zero write-lock contention.
-    /// Each submit only creates a unique Solution PDA.
-    pub fn submit_solution(ctx: Context<SubmitSolution>, text: String, nonce: u64, recipient: Pubkey) -> Result<()> {
+    /// Each submit only creates a unique Solution PDA, plus, when the
+    /// fingerprint index is on, writes its FingerprintShards.
+    ///
+    /// Remaining accounts: the active WordList if `mine_state.word_list` is set,
+    /// then (index on) each fingerprint block's shard pages up to the first one
+    /// not full, in block order (see `record_fingerprint`).
+    pub fn submit_solution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitSolution<'info>>,
+        text: String,
+        nonce: u64,
+        recipient: Pubkey,
+    ) -> Result<()> {
         let clock = Clock::get()?;
 
         // ── Read state (mine_state is read-only, no write lock) ──
@@ -77,6 +107,9 @@ pub mod proof_of_inference {
         let epoch_end_time = ctx.accounts.mine_state.epoch_end_time;
         let total_supply = ctx.accounts.mine_state.total_supply;
 
+        // ── Not paused ──
+        require_not_paused(&ctx.accounts.mine_state, PAUSE_SUBMIT)?;
+
         // ── Epoch must be active ──
         require!(
             clock.unix_timestamp < epoch_end_time,
@@ -86,36 +119,38 @@ pub mod proof_of_inference {
         // ── Supply cap ──
         require!(total_supply < MAX_SUPPLY, ErrorCode::MaxSupplyReached);
 
-        // ── Derive required words ──
-        let rw = words::derive_words(&challenge_seed, difficulty);
-        let w0 = &rw.words[0][..rw.lens[0]];
-        let w1 = &rw.words[1][..rw.lens[1]];
-        let w2 = &rw.words[2][..rw.lens[2]];
-        let w3 = &rw.words[3][..rw.lens[3]];
-        let w4 = &rw.words[4][..rw.lens[4]];
-        let w5 = &rw.words[5][..rw.lens[5]];
-        let w6 = &rw.words[6][..rw.lens[6]];
-        let w7 = &rw.words[7][..rw.lens[7]];
-        let all_words: [&[u8]; 8] = [w0, w1, w2, w3, w4, w5, w6, w7];
+        // ── Language and challenge ──
+        let rules = ctx.accounts.mine_state.active_text_rules().for_difficulty(difficulty);
+        let pack = lang::select(rules.languages, &challenge_seed);
+        let (word_list, shards) = open_word_list(&ctx.accounts.mine_state, ctx.remaining_accounts)?;
+        let packed = word_list.as_ref().map(ActiveWordList::words);
+        let list = pack.active_wordlist(ctx.accounts.mine_state.wordlist_version, packed.as_ref());
+        let challenge = challenge::derive(&rules, pack, list, &challenge_seed, difficulty);
+        let rw = challenge.required_words();
+        let mut all_words: [&[u8]; words::MAX_REQUIRED] = [&[]; words::MAX_REQUIRED];
+        for (w, (word, &len)) in all_words.iter_mut().zip(rw.words.iter().zip(rw.lens.iter())) {
+            *w = &word[..len];
+        }
         let active_words = &all_words[..rw.count];
 
         // ── Verify text constraints ──
-        require!(
-            verify::verify_text(text.as_bytes(), active_words),
-            ErrorCode::InvalidText
-        );
+        #[cfg(feature = "cu-log")]
+        anchor_lang::solana_program::log::sol_log_compute_units();
+        verify::verify_text_in(pack, text.as_bytes(), active_words, &rules).map_err(|rejection| {
+            if let verify::TextRejection::MissingRequiredWord(idx) = rejection {
+                msg!("Required word {} not found in order", idx);
+            }
+            ErrorCode::from(rejection)
+        })?;
+        challenge.verify(pack, text.as_bytes()).map_err(ErrorCode::from)?;
+        #[cfg(feature = "cu-log")]
+        anchor_lang::solana_program::log::sol_log_compute_units();
 
         // ── Compute hash ──
         let miner_key = ctx.accounts.miner.key();
-        let nonce_bytes = nonce.to_le_bytes();
-        let hash = keccak::hashv(&[
-            &challenge_seed,
-            miner_key.as_ref(),
-            text.as_bytes(),
-            b"||",
-            &nonce_bytes,
-        ]);
-        let hash_bytes = hash.to_bytes();
+        let hash_bytes = pow_hash(&challenge_seed, &miner_key.to_bytes(), text.as_bytes(), nonce);
+        #[cfg(feature = "cu-log")]
+        anchor_lang::solana_program::log::sol_log_compute_units();
 
         // ── Verify PoW difficulty ──
         require!(
@@ -123,6 +158,19 @@ pub mod proof_of_inference {
             ErrorCode::InsufficientDifficulty
         );
 
+        // ── Near-duplicate check ──
+        let fingerprint = verify::simhash(text.as_bytes());
+        if rules.fingerprint_shards > 0 {
+            record_fingerprint(
+                shards,
+                &ctx.accounts.miner.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                epoch_number,
+                fingerprint,
+                &rules,
+            )?;
+        }
+
         // ── Write Solution PDA ──
         let solution = &mut ctx.accounts.solution;
         solution.miner = miner_key;
@@ -131,6 +179,7 @@ pub mod proof_of_inference {
         solution.nonce = nonce;
         solution.hash = hash_bytes;
         solution.bump = ctx.bumps.solution;
+        solution.fingerprint = fingerprint;
 
         Ok(())
     }
@@ -156,19 +205,16 @@ pub mod proof_of_inference {
         // ── Read state ──
         let current_epoch = ctx.accounts.mine_state.epoch_number;
         let epoch_end_time = ctx.accounts.mine_state.epoch_end_time;
-        let total_mined = ctx.accounts.mine_state.total_mined;
-        let total_supply = ctx.accounts.mine_state.total_supply;
         let solution_epoch = ctx.accounts.solution.epoch;
 
+        // ── Not paused ──
+        require_not_paused(&ctx.accounts.mine_state, PAUSE_CLAIM)?;
+
         // ── Solution's epoch must have ended ──
-        let epoch_over = if solution_epoch < current_epoch {
-            true
-        } else if solution_epoch == current_epoch {
-            clock.unix_timestamp >= epoch_end_time
-        } else {
-            false
-        };
-        require!(epoch_over, ErrorCode::EpochNotEnded);
+        require!(
+            epoch_over(solution_epoch, current_epoch, epoch_end_time, clock.unix_timestamp),
+            ErrorCode::EpochNotEnded
+        );
 
         // ── Not expired ──
         require!(
@@ -176,9 +222,8 @@ pub mod proof_of_inference {
             ErrorCode::ClaimExpired
         );
 
-        // ── Calculate reward ──
-        let reward = calculate_reward(total_mined);
-        let actual_reward = reward.min(MAX_SUPPLY.saturating_sub(total_supply));
+        // ── Update mine state (reserve supply, no mint yet) ──
+        let actual_reward = reserve_reward(&mut ctx.accounts.mine_state);
 
         // ── Update vesting ──
         let vesting = &mut ctx.accounts.vesting;
@@ -189,11 +234,6 @@ pub mod proof_of_inference {
         // Add new reward to locked
         vesting.locked = vesting.locked.checked_add(actual_reward).unwrap();
 
-        // ── Update mine state (reserve supply, no mint yet) ──
-        let state = &mut ctx.accounts.mine_state;
-        state.total_mined += 1;
-        state.total_supply = state.total_supply.checked_add(actual_reward).unwrap();
-
         // Solution PDA closed by Anchor `close` constraint → rent to miner
         Ok(())
     }
@@ -201,17 +241,20 @@ pub mod proof_of_inference {
     /// Withdraw vested tokens.
     ///
     /// Calculates newly vested amount, then mints to recipient's token account.
+    ///
+    /// Blocked by PAUSE_WITHDRAW_UNLOCKED; vesting keeps accruing meanwhile.
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         let clock = Clock::get()?;
         let bump = ctx.accounts.mine_state.bump;
 
-        // ── Update vesting ──
-        let vesting = &mut ctx.accounts.vesting;
-        drip_vesting(vesting, clock.unix_timestamp);
+        // ── Not paused, update vesting ──
+        let amount = take_withdrawable(&ctx.accounts.mine_state, &mut ctx.accounts.vesting, clock.unix_timestamp)?;
 
-        let amount = vesting.unlocked;
-        require!(amount > 0, ErrorCode::NothingToWithdraw);
-        vesting.unlocked = 0;
+        // ── Supply invariant must hold after this mint ──
+        let supply_after = ctx.accounts.mint.supply
+            .checked_add(amount)
+            .ok_or(ErrorCode::SupplyInvariantViolated)?;
+        check_supply_invariant(supply_after, ctx.accounts.mine_state.total_supply)?;
 
         // ── CPI: mint tokens to recipient ──
         let seeds = &[b"mine_state".as_ref(), &[bump]];
@@ -238,6 +281,9 @@ pub mod proof_of_inference {
         let clock = Clock::get()?;
         let state = &mut ctx.accounts.mine_state;
 
+        // ── Not paused ──
+        require_not_paused(state, PAUSE_ADVANCE)?;
+
         // ── Current epoch must have ended ──
         require!(
             clock.unix_timestamp >= state.epoch_end_time,
@@ -251,13 +297,13 @@ pub mod proof_of_inference {
         let target = TARGET_SOLUTIONS;
         if solution_count > target + target / 5 {
             let ratio = solution_count / target;
-            let increase = log2_ceil(ratio).max(1).min(MAX_DIFFICULTY_ADJ);
+            let increase = log2_ceil(ratio).clamp(1, MAX_DIFFICULTY_ADJ);
             state.difficulty = state.difficulty.saturating_add(increase).min(MAX_DIFFICULTY);
         } else if solution_count == 0 {
             state.difficulty = state.difficulty.saturating_sub(MAX_DIFFICULTY_ADJ).max(MIN_DIFFICULTY);
         } else if solution_count < target.saturating_sub(target / 5) {
             let ratio = target / solution_count.max(1);
-            let decrease = log2_ceil(ratio).max(1).min(MAX_DIFFICULTY_ADJ);
+            let decrease = log2_ceil(ratio).clamp(1, MAX_DIFFICULTY_ADJ);
             state.difficulty = state.difficulty.saturating_sub(decrease).max(MIN_DIFFICULTY);
         }
 
@@ -284,6 +330,8 @@ pub mod proof_of_inference {
         let current_epoch = ctx.accounts.mine_state.epoch_number;
         let solution_epoch = ctx.accounts.solution.epoch;
 
+        require_not_paused(&ctx.accounts.mine_state, PAUSE_CLOSE)?;
+
         require!(
             current_epoch >= solution_epoch.saturating_add(CLAIM_EXPIRY_EPOCHS),
             ErrorCode::NotExpired
@@ -293,51 +341,274 @@ pub mod proof_of_inference {
         Ok(())
     }
 
-    /// Transfer crank authority to a new address.
-    pub fn set_crank_authority(ctx: Context<SetCrankAuthority>, new_authority: Pubkey) -> Result<()> {
-        ctx.accounts.mine_state.crank_authority = new_authority;
+    /// Close a FingerprintShard once its epoch is over. Permissionless; rent
+    /// goes back to the miner whose submit created it.
+    pub fn close_fingerprint_shard(ctx: Context<CloseFingerprintShard>) -> Result<()> {
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.mine_state;
+
+        require_not_paused(state, PAUSE_CLOSE)?;
+
+        require!(
+            epoch_over(ctx.accounts.shard.epoch, state.epoch_number, state.epoch_end_time, clock.unix_timestamp),
+            ErrorCode::EpochNotEnded
+        );
+
+        // Shard closed by Anchor `close` constraint → rent to payer
         Ok(())
     }
 
-    /// Create token metadata via Metaplex.
+    /// Propose a new crank authority (step 1 of 2).
+    ///
+    /// Takes effect only once `new_authority` signs `accept_authority`, so a typo
+    /// cannot lock the admin paths. Proposing `Pubkey::default()` cancels.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.mine_state.pending_authority = new_authority;
+        Ok(())
+    }
 
-    /// Reset mining state. Crank authority only. For re-initialization.
-    pub fn reset_state(ctx: Context<ResetState>) -> Result<()> {
-        let clock = Clock::get()?;
+    /// Accept a pending crank authority transfer (step 2 of 2). Signed by the new authority.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
         let state = &mut ctx.accounts.mine_state;
+        state.crank_authority = state.pending_authority;
+        state.pending_authority = Pubkey::default();
+        Ok(())
+    }
 
-        let seed_input = [
-            clock.slot.to_le_bytes().as_ref(),
-            clock.unix_timestamp.to_le_bytes().as_ref(),
-            state.key().as_ref(),
-        ]
-        .concat();
-        let challenge_seed = keccak::hash(&seed_input).to_bytes();
+    /// Permanently give up the crank authority.
+    ///
+    /// Sets the authority to `Pubkey::default()`, which nobody can sign for, so
+    /// `migrate_state`, `create_metadata` and further transfers are disabled for good.
+    pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+        let state = &mut ctx.accounts.mine_state;
+        state.crank_authority = Pubkey::default();
+        state.pending_authority = Pubkey::default();
+        Ok(())
+    }
 
-        state.total_mined = 0;
-        state.difficulty = INITIAL_DIFFICULTY;
-        state.challenge_seed = challenge_seed;
-        state.epoch_number = 0;
-        state.epoch_start_time = clock.unix_timestamp;
-        state.epoch_end_time = clock.unix_timestamp + EPOCH_DURATION;
-        state.solutions_in_epoch = 0;
-        state.settled_in_epoch = 0;
-        state.total_supply = 0;
-        // mint and crank_authority and bump stay the same
+    /// Check minted supply against committed supply and the cap (permissionless).
+    ///
+    /// Fails if `mint.supply > total_supply` or `total_supply > MAX_SUPPLY`.
+    /// The `SupplyAudit` event is emitted before the check, so a failed
+    /// simulation still shows the numbers in its logs.
+    pub fn audit_supply(ctx: Context<AuditSupply>) -> Result<()> {
+        let mint_supply = ctx.accounts.mint.supply;
+        let total_supply = ctx.accounts.mine_state.total_supply;
+
+        emit!(SupplyAudit {
+            mint_supply,
+            total_supply,
+            max_supply: MAX_SUPPLY,
+            unminted_committed: total_supply.saturating_sub(mint_supply),
+        });
+
+        check_supply_invariant(mint_supply, total_supply)
+    }
+
+    /// Set the pause bitmask (crank authority only). See the PAUSE_* constants.
+    pub fn set_pause(ctx: Context<SetPause>, paused: u8) -> Result<()> {
+        require!(paused & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags);
+        ctx.accounts.mine_state.paused = paused;
+        Ok(())
+    }
+
+    /// Replace the text verification thresholds (crank authority only).
+    pub fn set_text_rules(ctx: Context<SetTextRules>, rules: verify::TextRules) -> Result<()> {
+        require!(rules.is_valid(), ErrorCode::InvalidTextRules);
+        ctx.accounts.mine_state.text_rules = PaddedTextRules::new(&rules);
+        Ok(())
+    }
+
+    /// Select the English wordlist required words are drawn from (crank
+    /// authority only). See the words::WORDLIST_V* constants.
+    pub fn set_wordlist_version(ctx: Context<SetWordlistVersion>, version: u8) -> Result<()> {
+        require!(is_wordlist_version(version), ErrorCode::InvalidWordlistVersion);
+        ctx.accounts.mine_state.wordlist_version = version;
+        Ok(())
+    }
+
+    /// Create an empty, unsealed WordList PDA (`["word_list", authority, id]`).
+    ///
+    /// Anyone can build a list; it only affects mining once sealed and
+    /// activated by governance (`set_word_list` or a `SetWordList` proposal).
+    pub fn create_word_list(ctx: Context<CreateWordList>, id: u64) -> Result<()> {
+        let mut list = ctx.accounts.word_list.load_init()?;
+        list.authority = ctx.accounts.authority.key();
+        list.id = id;
+        list.bump = ctx.bumps.word_list;
+        Ok(())
+    }
+
+    /// Grow an unsealed WordList toward room for `capacity` words. An account
+    /// grows by at most 10 KiB per instruction, so repeat until it fits.
+    pub fn extend_word_list(ctx: Context<ExtendWordList>, capacity: u16) -> Result<()> {
+        require!(capacity as usize <= WORD_LIST_CAPACITY, ErrorCode::InvalidWordList);
+        require!(ctx.accounts.word_list.load()?.sealed == 0, ErrorCode::WordListSealed);
+
+        let info = ctx.accounts.word_list.to_account_info();
+        let target = WORD_LIST_HEADER + capacity as usize * words::MAX_WORD_LEN;
+        grow_account(
+            &info,
+            target.min(info.data_len() + MAX_PERMITTED_DATA_INCREASE),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Write `words` into slots `start..` of an unsealed WordList (its
+    /// authority only). Slots can be rewritten until the list is sealed.
+    pub fn write_word_list(ctx: Context<WriteWordList>, start: u16, words: Vec<String>) -> Result<()> {
+        require!(
+            words.iter().all(|w| w.is_ascii() && words::is_valid_word(w.as_bytes())),
+            ErrorCode::InvalidWordList
+        );
+        let info = ctx.accounts.word_list.to_account_info();
+        let end = start as usize + words.len();
+        require!(WORD_LIST_HEADER + end * words::MAX_WORD_LEN <= info.data_len(), ErrorCode::InvalidWordList);
+        {
+            let mut list = ctx.accounts.word_list.load_mut()?;
+            require!(list.sealed == 0, ErrorCode::WordListSealed);
+            list.count = list.count.max(end as u16);
+        }
+
+        let mut data = info.try_borrow_mut_data()?;
+        let slots = &mut data[WORD_LIST_HEADER + start as usize * words::MAX_WORD_LEN..];
+        for (slot, word) in slots.chunks_exact_mut(words::MAX_WORD_LEN).zip(words.iter()) {
+            slot.fill(0);
+            slot[..word.len()].copy_from_slice(word.as_bytes());
+        }
+        Ok(())
+    }
+
+    /// Seal a WordList, making it immutable (its authority only).
+    ///
+    /// The written words must pass `words::PackedWordlist::is_valid` under
+    /// `groups` and hash to `hash` (`PackedWordlist::hash`), which is what
+    /// governance approves when activating it.
+    pub fn seal_word_list(ctx: Context<WriteWordList>, groups: [u16; 4], hash: [u8; 32]) -> Result<()> {
+        let count = {
+            let list = ctx.accounts.word_list.load()?;
+            require!(list.sealed == 0, ErrorCode::WordListSealed);
+            list.count as usize
+        };
+        {
+            let info = ctx.accounts.word_list.to_account_info();
+            let data = info.try_borrow_data()?;
+            let slots = &data[WORD_LIST_HEADER..WORD_LIST_HEADER + count * words::MAX_WORD_LEN];
+            let packed = words::PackedWordlist::new(slots, groups.map(usize::from));
+            require!(packed.is_valid(), ErrorCode::InvalidWordList);
+            require!(packed.hash() == hash, ErrorCode::WordListHashMismatch);
+        }
+
+        let mut list = ctx.accounts.word_list.load_mut()?;
+        list.groups = groups;
+        list.hash = hash;
+        list.sealed = 1;
+        Ok(())
+    }
 
+    /// Activate a sealed WordList for English required words, or go back to
+    /// the built-in list (`wordlist_version`) when none is passed (crank
+    /// authority only).
+    pub fn set_word_list(ctx: Context<SetWordList>) -> Result<()> {
+        let word_list = match &ctx.accounts.word_list {
+            Some(list) => {
+                require!(list.load()?.sealed != 0, ErrorCode::WordListNotSealed);
+                list.key()
+            }
+            None => Pubkey::default(),
+        };
+        ctx.accounts.mine_state.word_list = word_list;
         Ok(())
     }
 
+    /// Preview a vesting account at the current clock (view, no state change).
+    ///
+    /// Returns locked/unlocked/claimable as `withdraw` would see them right now.
+    /// Intended to be called through `simulateTransaction`.
+    pub fn preview_vesting(ctx: Context<PreviewVesting>) -> Result<VestingPreview> {
+        let clock = Clock::get()?;
+        Ok(vesting_preview(&ctx.accounts.vesting, clock.unix_timestamp))
+    }
+
+    /// Current challenge: required words, difficulty and time left (view, no state change).
+    ///
+    /// Remaining accounts: the active WordList if `mine_state.word_list` is set.
+    pub fn current_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CurrentChallenge<'info>>,
+    ) -> Result<ChallengeInfo> {
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.mine_state;
+        let (word_list, _) = open_word_list(state, ctx.remaining_accounts)?;
+        let packed = word_list.as_ref().map(ActiveWordList::words);
+        Ok(challenge_info(state, packed.as_ref(), clock.unix_timestamp))
+    }
+
+    /// Preview the reward `claim` would grant for a Solution (view, no state change).
+    pub fn preview_claim(ctx: Context<PreviewClaim>) -> Result<ClaimPreview> {
+        let clock = Clock::get()?;
+        Ok(claim_preview(&ctx.accounts.mine_state, ctx.accounts.solution.epoch, clock.unix_timestamp))
+    }
+
+    /// Grow a MineState created by an older program version to the current layout.
+    ///
+    /// Permissionless: the payer covers the extra rent. New fields are zeroed,
+    /// which is their "unset" value. No-op if the account is already current.
+    pub fn resize_state(ctx: Context<ResizeState>) -> Result<()> {
+        let info = ctx.accounts.mine_state.to_account_info();
+        require!(info.owner == &crate::ID, ErrorCode::Unauthorized);
+        grow_account(
+            &info,
+            8 + MineState::INIT_SPACE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Grow a Solution created before fingerprints were recorded to the current
+    /// layout, so it can be claimed or closed.
+    ///
+    /// Permissionless: the payer covers the extra rent, which goes to the miner
+    /// (or closer) with the rest when the Solution is closed. The fingerprint
+    /// stays 0 (not recorded). No-op if the account is already current.
+    pub fn resize_solution(ctx: Context<ResizeSolution>) -> Result<()> {
+        let info = ctx.accounts.solution.to_account_info();
+        require!(info.owner == &crate::ID, ErrorCode::Unauthorized);
+        require!(
+            info.try_borrow_data()?.starts_with(&<Solution as anchor_lang::Discriminator>::DISCRIMINATOR),
+            ErrorCode::Unauthorized
+        );
+        grow_account(
+            &info,
+            8 + Solution::INIT_SPACE,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Migrate mining state after a program upgrade. Crank authority only.
+    ///
+    /// Replaces the old `reset_state`, which zeroed `total_supply` while minted and
+    /// vesting tokens still existed. Committed supply is kept, or raised to
+    /// `mint.supply` + outstanding vesting if that is higher; `total_mined` (the
+    /// halving position) and difficulty are kept; a fresh epoch starts with a new seed.
+    ///
+    /// Remaining accounts: VestingAccounts with a balance, sorted by address.
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        let clock = Clock::get()?;
+        let outstanding = outstanding_vesting(ctx.remaining_accounts)?;
+        let mint_supply = ctx.accounts.mint.supply;
+        let state_key = ctx.accounts.mine_state.key();
+        migrate_mine_state(&mut ctx.accounts.mine_state, &state_key, &clock, mint_supply, outstanding)
+    }
+
+    /// Create token metadata via Metaplex.
     pub fn create_metadata(
         ctx: Context<CreateMetadata>,
         name: String,
         symbol: String,
         uri: String,
     ) -> Result<()> {
-        let bump = ctx.accounts.mine_state.bump;
-        let seeds = &[b"mine_state".as_ref(), &[bump]];
-        let signer_seeds = &[&seeds[..]];
-
         let metadata_accounts = mpl_token_metadata::instructions::CreateMetadataAccountV3CpiAccounts {
             metadata: &ctx.accounts.metadata.to_account_info(),
             mint: &ctx.accounts.mint.to_account_info(),
@@ -348,282 +619,1454 @@ pub mod proof_of_inference {
             rent: Some(&ctx.accounts.rent.to_account_info()),
         };
 
-        let data_v2 = mpl_token_metadata::types::DataV2 {
+        invoke_create_metadata(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            metadata_accounts,
+            ctx.accounts.mine_state.bump,
             name,
             symbol,
             uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        };
+        )
+    }
 
-        mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi::new(
+    /// Update the token's name, symbol and URI (crank authority only).
+    /// Fails once `finalize_metadata` has made the metadata immutable.
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        invoke_update_metadata(
             &ctx.accounts.token_metadata_program.to_account_info(),
-            metadata_accounts,
-            mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
-                data: data_v2,
-                is_mutable: true,
-                collection_details: None,
-            },
-        ).invoke_signed(signer_seeds)?;
+            &ctx.accounts.metadata.to_account_info(),
+            &ctx.accounts.mine_state.to_account_info(),
+            ctx.accounts.mine_state.bump,
+            Some(metadata_data(name, symbol, uri)),
+            None,
+        )
+    }
 
-        Ok(())
+    /// Permanently lock the token metadata (`is_mutable = false`). Crank authority only.
+    pub fn finalize_metadata(ctx: Context<UpdateMetadata>) -> Result<()> {
+        invoke_update_metadata(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            &ctx.accounts.metadata.to_account_info(),
+            &ctx.accounts.mine_state.to_account_info(),
+            ctx.accounts.mine_state.bump,
+            None,
+            Some(false),
+        )
     }
-}
 
-// ============================================================
-// Helpers
-// ============================================================
+    // ── Multisig admin ──
 
-/// Drip vesting: move locked → unlocked based on elapsed time.
-fn drip_vesting(v: &mut Account<VestingAccount>, now: i64) {
-    if v.locked == 0 || now <= v.last_update {
-        v.last_update = now;
-        return;
-    }
-    let elapsed = now - v.last_update;
-    let release = if elapsed >= VESTING_DURATION {
-        v.locked
-    } else {
-        // Use u128 to avoid overflow
-        (v.locked as u128 * elapsed as u128 / VESTING_DURATION as u128) as u64
-    };
-    v.unlocked += release;
-    v.locked -= release;
-    v.last_update = now;
-}
-
-/// Reward with halving: INITIAL_REWARD >> (total_mined / HALVING_INTERVAL)
-fn calculate_reward(total_mined: u64) -> u64 {
-    let halvings = total_mined / HALVING_INTERVAL;
-    if halvings >= 64 {
-        return 0;
+    /// Hand the admin role to an M-of-N `AdminSet` (current authority only).
+    ///
+    /// `crank_authority` becomes the AdminSet PDA, which no key can sign for, so the
+    /// single-signer admin instructions stop working and every admin action must go
+    /// through `propose_admin_action` → `approve_admin_action` → `execute_*`.
+    pub fn create_admin_set(ctx: Context<CreateAdminSet>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        validate_admin_set(&members, threshold)?;
+
+        let admin_set = &mut ctx.accounts.admin_set;
+        admin_set.members = members;
+        admin_set.threshold = threshold;
+        admin_set.generation = 0;
+        admin_set.proposal_count = 0;
+        admin_set.bump = ctx.bumps.admin_set;
+
+        ctx.accounts.mine_state.crank_authority = admin_set.key();
+        ctx.accounts.mine_state.pending_authority = Pubkey::default();
+        Ok(())
     }
-    INITIAL_REWARD >> halvings
-}
 
-/// Check that hash has at least `difficulty` leading zero bits.
-fn check_difficulty(hash: &[u8; 32], difficulty: u64) -> bool {
-    let full_bytes = (difficulty / 8) as usize;
-    let remaining_bits = (difficulty % 8) as u8;
-
-    for i in 0..full_bytes {
-        if i >= 32 {
-            return false;
-        }
-        if hash[i] != 0 {
-            return false;
+    /// Propose an admin action. Any member may propose; the proposer's approval is counted.
+    pub fn propose_admin_action(ctx: Context<ProposeAdminAction>, action: AdminAction) -> Result<()> {
+        let admin_set = &mut ctx.accounts.admin_set;
+        let idx = admin_set.member_index(&ctx.accounts.proposer.key())?;
+
+        match &action {
+            AdminAction::UpdateAdminSet { members, threshold } => validate_admin_set(members, *threshold)?,
+            AdminAction::SetPause { paused } => require!(paused & !PAUSE_ALL == 0, ErrorCode::InvalidPauseFlags),
+            AdminAction::SetTextRules { rules } => {
+                require!(rules.rules().is_some_and(|r| r.is_valid()), ErrorCode::InvalidTextRules)
+            }
+            AdminAction::SetWordlistVersion { version } => {
+                require!(is_wordlist_version(*version), ErrorCode::InvalidWordlistVersion)
+            }
+            AdminAction::SetWordList { word_list, hash } => {
+                require!(*word_list != Pubkey::default() || *hash == [0; 32], ErrorCode::InvalidWordList)
+            }
+            _ => {}
         }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = admin_set.proposal_count;
+        proposal.generation = admin_set.generation;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action;
+        proposal.approvals = 1 << idx;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
+
+        admin_set.proposal_count += 1;
+        Ok(())
     }
 
-    if remaining_bits > 0 && full_bytes < 32 {
-        let mask = 0xFF << (8 - remaining_bits);
-        if hash[full_bytes] & mask != 0 {
-            return false;
-        }
+    /// Approve a pending admin proposal (members only).
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        let idx = ctx.accounts.admin_set.member_index(&ctx.accounts.member.key())?;
+        ctx.accounts.proposal.approvals |= 1 << idx;
+        Ok(())
     }
 
-    true
-}
+    /// Execute an approved `AdminAction::MigrateState`. Remaining accounts as in `migrate_state`.
+    pub fn execute_migrate_state(ctx: Context<ExecuteMigrateState>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        require!(matches!(action, AdminAction::MigrateState), ErrorCode::ProposalActionMismatch);
 
-/// Ceiling of log2(n), minimum 1.
-fn log2_ceil(n: u64) -> u64 {
-    if n <= 1 {
-        return 0;
+        let clock = Clock::get()?;
+        let outstanding = outstanding_vesting(ctx.remaining_accounts)?;
+        let mint_supply = ctx.accounts.mint.supply;
+        let state_key = ctx.accounts.mine_state.key();
+        migrate_mine_state(&mut ctx.accounts.mine_state, &state_key, &clock, mint_supply, outstanding)
     }
-    64 - (n - 1).leading_zeros() as u64
-}
 
-// ============================================================
-// Account Structs
-// ============================================================
+    /// Execute an approved `AdminAction::ProposeAuthority` (the new authority still has to accept).
+    pub fn execute_propose_authority(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::ProposeAuthority { new_authority } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + MineState::INIT_SPACE,
-        seeds = [b"mine_state"],
-        bump,
-    )]
-    pub mine_state: Account<'info, MineState>,
+        ctx.accounts.mine_state.pending_authority = new_authority;
+        Ok(())
+    }
 
-    #[account(
-        init,
-        payer = payer,
-        mint::decimals = 3,
-        mint::authority = mine_state,
-        seeds = [b"mint"],
-        bump,
-    )]
-    pub mint: Account<'info, Mint>,
+    /// Execute an approved `AdminAction::RenounceAuthority`.
+    pub fn execute_renounce_authority(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        require!(matches!(action, AdminAction::RenounceAuthority), ErrorCode::ProposalActionMismatch);
 
-    #[account(mut)]
-    pub payer: Signer<'info>,
+        let state = &mut ctx.accounts.mine_state;
+        state.crank_authority = Pubkey::default();
+        state.pending_authority = Pubkey::default();
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// Execute an approved `AdminAction::SetPause`.
+    pub fn execute_set_pause(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::SetPause { paused } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
 
-#[derive(Accounts)]
-pub struct SubmitSolution<'info> {
-    // READ-ONLY: no write lock acquired
-    #[account(
-        seeds = [b"mine_state"],
-        bump = mine_state.bump,
-    )]
-    pub mine_state: Account<'info, MineState>,
+        ctx.accounts.mine_state.paused = paused;
+        Ok(())
+    }
 
-    #[account(
-        init,
-        payer = miner,
-        space = 8 + Solution::INIT_SPACE,
-        seeds = [b"solution", miner.key().as_ref(), &mine_state.epoch_number.to_le_bytes()],
-        bump,
-    )]
-    pub solution: Account<'info, Solution>,
+    /// Execute an approved `AdminAction::SetTextRules`.
+    pub fn execute_set_text_rules(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::SetTextRules { rules } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
+        let rules = rules.rules().filter(verify::TextRules::is_valid).ok_or(ErrorCode::InvalidTextRules)?;
 
-    #[account(mut)]
-    pub miner: Signer<'info>,
+        ctx.accounts.mine_state.text_rules = PaddedTextRules::new(&rules);
+        Ok(())
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    /// Execute an approved `AdminAction::SetWordlistVersion`.
+    pub fn execute_set_wordlist_version(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::SetWordlistVersion { version } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
 
-#[derive(Accounts)]
-pub struct CreateVesting<'info> {
-    #[account(
-        init,
-        payer = miner,
-        space = 8 + VestingAccount::INIT_SPACE,
-        seeds = [b"vesting", miner.key().as_ref()],
-        bump,
-    )]
-    pub vesting: Account<'info, VestingAccount>,
+        ctx.accounts.mine_state.wordlist_version = version;
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub miner: Signer<'info>,
+    /// Execute an approved `AdminAction::SetWordList`. The WordList must be
+    /// passed, sealed, with the approved hash; `Pubkey::default()` goes back to
+    /// the built-in list.
+    pub fn execute_set_word_list(ctx: Context<ExecuteSetWordList>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::SetWordList { word_list, hash } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
 
-    pub system_program: Program<'info, System>,
-}
+        if word_list != Pubkey::default() {
+            let list = ctx.accounts.word_list.as_ref().ok_or_else(|| error!(ErrorCode::MissingWordList))?;
+            require_keys_eq!(list.key(), word_list, ErrorCode::MissingWordList);
+            let list = list.load()?;
+            require!(list.sealed != 0, ErrorCode::WordListNotSealed);
+            require!(list.hash == hash, ErrorCode::WordListHashMismatch);
+        }
+        ctx.accounts.mine_state.word_list = word_list;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Claim<'info> {
-    #[account(
-        mut,
-        seeds = [b"mine_state"],
-        bump = mine_state.bump,
-    )]
-    pub mine_state: Account<'info, MineState>,
+    /// Execute an approved `AdminAction::UpdateAdminSet`.
+    ///
+    /// Bumps the generation, which invalidates every proposal made under the old set.
+    pub fn execute_update_admin_set(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::UpdateAdminSet { members, threshold } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
 
-    #[account(
-        mut,
-        seeds = [b"solution", solution.miner.as_ref(), &solution.epoch.to_le_bytes()],
-        bump = solution.bump,
-        close = miner,
-    )]
+        let admin_set = &mut ctx.accounts.admin_set;
+        admin_set.members = members;
+        admin_set.threshold = threshold;
+        admin_set.generation += 1;
+        Ok(())
+    }
+
+    /// Execute an approved `AdminAction::UpdateMetadata`.
+    pub fn execute_update_metadata(ctx: Context<ExecuteUpdateMetadata>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::UpdateMetadata { name, symbol, uri } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
+
+        invoke_update_metadata(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            &ctx.accounts.metadata.to_account_info(),
+            &ctx.accounts.mine_state.to_account_info(),
+            ctx.accounts.mine_state.bump,
+            Some(metadata_data(name, symbol, uri)),
+            None,
+        )
+    }
+
+    /// Execute an approved `AdminAction::FinalizeMetadata`.
+    pub fn execute_finalize_metadata(ctx: Context<ExecuteUpdateMetadata>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        require!(matches!(action, AdminAction::FinalizeMetadata), ErrorCode::ProposalActionMismatch);
+
+        invoke_update_metadata(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            &ctx.accounts.metadata.to_account_info(),
+            &ctx.accounts.mine_state.to_account_info(),
+            ctx.accounts.mine_state.bump,
+            None,
+            Some(false),
+        )
+    }
+
+    /// Execute an approved `AdminAction::CreateMetadata`. The executor pays for the metadata account.
+    pub fn execute_create_metadata(ctx: Context<ExecuteCreateMetadata>) -> Result<()> {
+        let action = take_approved(&mut ctx.accounts.proposal, &ctx.accounts.admin_set)?;
+        let AdminAction::CreateMetadata { name, symbol, uri } = action else {
+            return err!(ErrorCode::ProposalActionMismatch);
+        };
+
+        let metadata_accounts = mpl_token_metadata::instructions::CreateMetadataAccountV3CpiAccounts {
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            mint: &ctx.accounts.mint.to_account_info(),
+            mint_authority: &ctx.accounts.mine_state.to_account_info(),
+            payer: &ctx.accounts.payer.to_account_info(),
+            update_authority: (&ctx.accounts.mine_state.to_account_info(), true),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            rent: Some(&ctx.accounts.rent.to_account_info()),
+        };
+
+        invoke_create_metadata(
+            &ctx.accounts.token_metadata_program.to_account_info(),
+            metadata_accounts,
+            ctx.accounts.mine_state.bump,
+            name,
+            symbol,
+            uri,
+        )
+    }
+}
+
+// ============================================================
+// Helpers
+// ============================================================
+
+/// Minted supply never exceeds committed supply, and committed supply never exceeds the cap.
+fn check_supply_invariant(mint_supply: u64, total_supply: u64) -> Result<()> {
+    require!(mint_supply <= total_supply, ErrorCode::SupplyInvariantViolated);
+    require!(total_supply <= MAX_SUPPLY, ErrorCode::SupplyInvariantViolated);
+    Ok(())
+}
+
+/// A `MineState::wordlist_version` governance may select (0 is left to old accounts).
+fn is_wordlist_version(version: u8) -> bool {
+    (words::WORDLIST_V1..=words::LATEST_WORDLIST).contains(&version)
+}
+
+/// Data of the active WordList, borrowed for the instruction.
+struct ActiveWordList<'info> {
+    data: Ref<'info, &'info mut [u8]>,
+    groups: [usize; 4],
+    count: usize,
+}
+
+impl ActiveWordList<'_> {
+    fn words(&self) -> words::PackedWordlist<'_> {
+        let end = WORD_LIST_HEADER + self.count * words::MAX_WORD_LEN;
+        words::PackedWordlist::new(&self.data[WORD_LIST_HEADER..end], self.groups)
+    }
+}
+
+/// Open the active WordList, which must be the first of `accounts` when
+/// `MineState::word_list` is set. Returns it (if any) and the accounts after it.
+fn open_word_list<'info>(
+    state: &MineState,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<(Option<ActiveWordList<'info>>, &'info [AccountInfo<'info>])> {
+    if state.word_list == Pubkey::default() {
+        return Ok((None, accounts));
+    }
+    let info = accounts.first().ok_or_else(|| error!(ErrorCode::MissingWordList))?;
+    require_keys_eq!(info.key(), state.word_list, ErrorCode::MissingWordList);
+
+    // Only sealed lists are activated, and a sealed list never changes
+    let list = AccountLoader::<WordList>::try_from(info)?;
+    let header = list.load()?;
+    let (groups, count) = (header.groups.map(usize::from), header.count as usize);
+    drop(header);
+
+    let data = info.try_borrow_data()?;
+    Ok((Some(ActiveWordList { data, groups, count }), &accounts[1..]))
+}
+
+/// Fail with `Paused` if any of `flags` is set in the pause bitmask.
+fn require_not_paused(state: &MineState, flags: u8) -> Result<()> {
+    require!(state.paused & flags == 0, ErrorCode::Paused);
+    Ok(())
+}
+
+/// Drip `v` up to `now` and take its whole unlocked balance for `withdraw`.
+/// PAUSE_WITHDRAW_UNLOCKED refuses it and leaves `v` undripped; the time
+/// vested meanwhile is released on the first withdraw after unpausing.
+fn take_withdrawable(state: &MineState, v: &mut VestingAccount, now: i64) -> Result<u64> {
+    require_not_paused(state, PAUSE_WITHDRAW_UNLOCKED)?;
+    drip_vesting(v, now);
+    let amount = v.unlocked;
+    require!(amount > 0, ErrorCode::NothingToWithdraw);
+    v.unlocked = 0;
+    Ok(amount)
+}
+
+/// Drip vesting: move locked → unlocked based on elapsed time.
+fn drip_vesting(v: &mut VestingAccount, now: i64) {
+    poi_core::mining::drip_vesting(&mut v.locked, &mut v.unlocked, &mut v.last_update, now);
+}
+
+/// A solution's epoch is over once the crank has moved past it,
+/// or it is the current epoch and its end time has passed.
+fn epoch_over(solution_epoch: u64, current_epoch: u64, epoch_end_time: i64, now: i64) -> bool {
+    if solution_epoch < current_epoch {
+        true
+    } else if solution_epoch == current_epoch {
+        now >= epoch_end_time
+    } else {
+        false
+    }
+}
+
+/// Reserve the reward for one claim: bump the halving position and commit supply.
+/// Never commits past MAX_SUPPLY.
+fn reserve_reward(state: &mut MineState) -> u64 {
+    let reward = calculate_reward(state.total_mined);
+    let actual_reward = reward.min(MAX_SUPPLY.saturating_sub(state.total_supply));
+    state.total_mined += 1;
+    state.total_supply = state.total_supply.checked_add(actual_reward).unwrap();
+    actual_reward
+}
+
+/// `preview_vesting` at `now`.
+fn vesting_preview(v: &VestingAccount, now: i64) -> VestingPreview {
+    let release = vested_amount(v.locked, v.last_update, now);
+
+    VestingPreview {
+        locked: v.locked - release,
+        unlocked: v.unlocked,
+        claimable: v.unlocked + release,
+        // Without further claims, everything locked has dripped by last_update + VESTING_DURATION
+        fully_vested_at: if v.locked == 0 { v.last_update } else { v.last_update + VESTING_DURATION },
+    }
+}
+
+/// `current_challenge` at `now`; `word_list` is the active WordList, if any.
+fn challenge_info(state: &MineState, word_list: Option<&words::PackedWordlist>, now: i64) -> ChallengeInfo {
+    let text_rules = state.active_text_rules().for_difficulty(state.difficulty);
+    let pack = lang::select(text_rules.languages, &state.challenge_seed);
+    let list = pack.active_wordlist(state.wordlist_version, word_list);
+    let challenge = challenge::derive(&text_rules, pack, list, &state.challenge_seed, state.difficulty);
+    let rw = challenge.required_words();
+    let mut required_words = Vec::with_capacity(rw.count);
+    for i in 0..rw.count {
+        required_words.push(String::from_utf8_lossy(&rw.words[i][..rw.lens[i]]).into_owned());
+    }
+
+    ChallengeInfo {
+        epoch_number: state.epoch_number,
+        difficulty: state.difficulty,
+        challenge_seed: state.challenge_seed,
+        required_words,
+        epoch_end_time: state.epoch_end_time,
+        seconds_left: state.epoch_end_time.saturating_sub(now).max(0),
+        paused: state.paused,
+        text_rules,
+        language: pack.code.to_string(),
+        challenge_kind: challenge.kind(),
+        challenge: challenge.describe(),
+        wordlist_version: state.wordlist_version,
+        word_list: state.word_list,
+    }
+}
+
+/// `preview_claim` at `now` for a Solution from `solution_epoch`.
+fn claim_preview(state: &MineState, solution_epoch: u64, now: i64) -> ClaimPreview {
+    let reward = calculate_reward(state.total_mined);
+
+    ClaimPreview {
+        epoch_over: epoch_over(solution_epoch, state.epoch_number, state.epoch_end_time, now),
+        expired: state.epoch_number >= solution_epoch.saturating_add(CLAIM_EXPIRY_EPOCHS),
+        expires_at_epoch: solution_epoch.saturating_add(CLAIM_EXPIRY_EPOCHS),
+        reward: reward.min(MAX_SUPPLY.saturating_sub(state.total_supply)),
+    }
+}
+
+/// Supply-preserving migration: committed supply never goes down, the halving
+/// position and difficulty are kept, and a fresh epoch starts with a new seed.
+///
+/// The epoch number moves forward rather than back to 0 so Solution PDA seeds
+/// (`["solution", miner, epoch]`) from before the migration are never reused.
+fn migrate_mine_state(
+    state: &mut MineState,
+    state_key: &Pubkey,
+    clock: &Clock,
+    mint_supply: u64,
+    outstanding_vesting: u64,
+) -> Result<()> {
+    let committed = mint_supply
+        .checked_add(outstanding_vesting)
+        .ok_or(ErrorCode::SupplyInvariantViolated)?;
+    require!(committed <= MAX_SUPPLY, ErrorCode::SupplyInvariantViolated);
+
+    let seed_input = [
+        state.challenge_seed.as_ref(),
+        clock.slot.to_le_bytes().as_ref(),
+        clock.unix_timestamp.to_le_bytes().as_ref(),
+        state_key.as_ref(),
+    ]
+    .concat();
+    state.challenge_seed = keccak::hash(&seed_input).to_bytes();
+
+    state.total_supply = state.total_supply.max(committed);
+    state.epoch_number += 1;
+    state.epoch_start_time = clock.unix_timestamp;
+    state.epoch_end_time = clock.unix_timestamp + EPOCH_DURATION;
+    state.solutions_in_epoch = 0;
+    // total_mined, difficulty, mint, authorities and bump stay the same
+
+    Ok(())
+}
+
+/// Sum locked + unlocked over VestingAccounts passed as remaining accounts.
+/// Accounts must be owned by this program and strictly sorted by address,
+/// so none can be counted twice.
+fn outstanding_vesting(accounts: &[AccountInfo]) -> Result<u64> {
+    let mut total: u64 = 0;
+    let mut prev: Option<Pubkey> = None;
+    for info in accounts {
+        require!(info.owner == &crate::ID, ErrorCode::InvalidVestingAccount);
+        if let Some(p) = prev {
+            require!(p < *info.key, ErrorCode::InvalidVestingAccount);
+        }
+        prev = Some(*info.key);
+
+        let data = info.try_borrow_data()?;
+        let v = VestingAccount::try_deserialize(&mut &data[..])
+            .map_err(|_| error!(ErrorCode::InvalidVestingAccount))?;
+        total = total
+            .checked_add(v.locked)
+            .and_then(|t| t.checked_add(v.unlocked))
+            .ok_or(ErrorCode::SupplyInvariantViolated)?;
+    }
+    Ok(total)
+}
+
+/// Realloc a program-owned account up to `new_len` (zero-filled), topping up
+/// rent from `payer`. No-op if it is already that long.
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if info.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let lamports_needed = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if lamports_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            lamports_needed,
+        )?;
+    }
+    info.realloc(new_len, true)?;
+
+    Ok(())
+}
+
+/// Check `fingerprint` against one page of a shard and add it if the page has
+/// room. Returns false for a full page: the fingerprint goes on the next one.
+fn index_fingerprint(page: &mut FingerprintShard, fingerprint: u64, max_distance: u8) -> Result<bool> {
+    let near = page.fingerprints.iter().any(|&earlier| {
+        verify::hamming_distance(earlier, fingerprint) <= max_distance as u32
+    });
+    require!(!near, ErrorCode::NearDuplicate);
+    if page.fingerprints.len() >= FINGERPRINTS_PER_SHARD {
+        return Ok(false);
+    }
+    page.fingerprints.push(fingerprint);
+    Ok(true)
+}
+
+/// Check `fingerprint` against this epoch's SimHash index, then add it.
+///
+/// The fingerprint is split into `near_duplicate_bits + 1` blocks (see
+/// `verify::fingerprint_block`). Any earlier fingerprint within that distance
+/// agrees with it on at least one block, so it was recorded in the same shard
+/// (`verify::fingerprint_shard`). A shard is a chain of pages,
+/// `["fingerprints", epoch, block, shard, page]`: every full page is checked and
+/// the fingerprint goes on the first one with room. `shards` are, block by block,
+/// that shard's pages from 0 up to the first non-full one; a missing page is
+/// created at `payer`'s expense. Submits only contend when they share a page,
+/// never on mine_state.
+fn record_fingerprint<'info>(
+    shards: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    epoch: u64,
+    fingerprint: u64,
+    rules: &verify::TextRules,
+) -> Result<()> {
+    let mut pages = shards.iter();
+
+    for block in 0..rules.near_duplicate_bits + 1 {
+        let shard_id = verify::fingerprint_shard(fingerprint, block, rules);
+        let mut page = 0u8;
+        loop {
+            let info = pages.next().ok_or_else(|| error!(ErrorCode::InvalidFingerprintShard))?;
+            let mut shard = open_fingerprint_page(info, payer, system_program, epoch, block, shard_id, page)?;
+            if index_fingerprint(&mut shard, fingerprint, rules.near_duplicate_bits)? {
+                shard.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+                break;
+            }
+            page = page.checked_add(1).ok_or_else(|| error!(ErrorCode::FingerprintShardFull))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Load page `page` of a shard of the epoch index, creating it if it doesn't exist.
+fn open_fingerprint_page<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    epoch: u64,
+    block: u8,
+    shard: u8,
+    page: u8,
+) -> Result<FingerprintShard> {
+    let epoch_bytes = epoch.to_le_bytes();
+
+    if info.owner == &crate::ID {
+        let existing = FingerprintShard::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let expected = Pubkey::create_program_address(
+            &[b"fingerprints", &epoch_bytes, &[block], &[shard], &[page], &[existing.bump]],
+            &crate::ID,
+        ).map_err(|_| error!(ErrorCode::InvalidFingerprintShard))?;
+        require_keys_eq!(info.key(), expected, ErrorCode::InvalidFingerprintShard);
+        return Ok(existing);
+    }
+
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"fingerprints", &epoch_bytes, &[block], &[shard], &[page]],
+        &crate::ID,
+    );
+    require_keys_eq!(info.key(), expected, ErrorCode::InvalidFingerprintShard);
+    create_pda(
+        info,
+        8 + FingerprintShard::INIT_SPACE,
+        &[b"fingerprints", &epoch_bytes, &[block], &[shard], &[page], &[bump]],
+        payer,
+        system_program,
+    )?;
+    Ok(FingerprintShard {
+        epoch,
+        block,
+        shard,
+        page,
+        payer: payer.key(),
+        bump,
+        fingerprints: Vec::new(),
+    })
+}
+
+/// Create a program-owned PDA of `space` bytes, rent paid by `payer`.
+///
+/// Like Anchor's `init`, works even if someone has already sent lamports to
+/// the address, so a pre-funded PDA can't block creation.
+fn create_pda<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer_seeds = &[seeds];
+
+    if info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount { from: payer.clone(), to: info.clone() },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let lamports_needed = rent.saturating_sub(info.lamports());
+    if lamports_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: info.clone() },
+            ),
+            lamports_needed,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate { account_to_allocate: info.clone() },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign { account_to_assign: info.clone() },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
+
+/// CPI into Metaplex CreateMetadataAccountV3, signed by the mine_state PDA.
+fn invoke_create_metadata<'info, 'a>(
+    token_metadata_program: &'a AccountInfo<'info>,
+    metadata_accounts: mpl_token_metadata::instructions::CreateMetadataAccountV3CpiAccounts<'info, 'a>,
+    bump: u8,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let seeds = &[b"mine_state".as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi::new(
+        token_metadata_program,
+        metadata_accounts,
+        mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
+            data: metadata_data(name, symbol, uri),
+            is_mutable: true,
+            collection_details: None,
+        },
+    ).invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// CPI into Metaplex UpdateMetadataAccountV2, signed by the mine_state PDA (the update authority).
+fn invoke_update_metadata<'info, 'a>(
+    token_metadata_program: &'a AccountInfo<'info>,
+    metadata: &'a AccountInfo<'info>,
+    mine_state: &'a AccountInfo<'info>,
+    bump: u8,
+    data: Option<mpl_token_metadata::types::DataV2>,
+    is_mutable: Option<bool>,
+) -> Result<()> {
+    let seeds = &[b"mine_state".as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    mpl_token_metadata::instructions::UpdateMetadataAccountV2Cpi::new(
+        token_metadata_program,
+        mpl_token_metadata::instructions::UpdateMetadataAccountV2CpiAccounts {
+            metadata,
+            update_authority: mine_state,
+        },
+        mpl_token_metadata::instructions::UpdateMetadataAccountV2InstructionArgs {
+            data,
+            new_update_authority: None,
+            primary_sale_happened: None,
+            is_mutable,
+        },
+    ).invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// Token metadata body: no royalties, creators, collection or uses.
+fn metadata_data(name: String, symbol: String, uri: String) -> mpl_token_metadata::types::DataV2 {
+    mpl_token_metadata::types::DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    }
+}
+
+/// Members must be unique and 1..=MAX_ADMINS; threshold must be 1..=members.
+fn validate_admin_set(members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_ADMINS,
+        ErrorCode::InvalidAdminSet
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        ErrorCode::InvalidAdminSet
+    );
+    for (i, m) in members.iter().enumerate() {
+        require!(!members[..i].contains(m), ErrorCode::InvalidAdminSet);
+    }
+    Ok(())
+}
+
+/// Check a proposal has reached threshold, mark it executed and hand back its action.
+fn take_approved(proposal: &mut Account<AdminProposal>, admin_set: &AdminSet) -> Result<AdminAction> {
+    require!(
+        proposal.approvals.count_ones() >= admin_set.threshold as u32,
+        ErrorCode::ThresholdNotMet
+    );
+    proposal.executed = true;
+    Ok(proposal.action.clone())
+}
+
+/// Ceiling of log2(n), minimum 1.
+fn log2_ceil(n: u64) -> u64 {
+    if n <= 1 {
+        return 0;
+    }
+    64 - (n - 1).leading_zeros() as u64
+}
+
+// ============================================================
+// Account Structs
+// ============================================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MineState::INIT_SPACE,
+        seeds = [b"mine_state"],
+        bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 3,
+        mint::authority = mine_state,
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitSolution<'info> {
+    // READ-ONLY: no write lock acquired
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        init,
+        payer = miner,
+        space = 8 + Solution::INIT_SPACE,
+        seeds = [b"solution", miner.key().as_ref(), &mine_state.epoch_number.to_le_bytes()],
+        bump,
+    )]
+    pub solution: Account<'info, Solution>,
+
+    #[account(mut)]
+    pub miner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVesting<'info> {
+    #[account(
+        init,
+        payer = miner,
+        space = 8 + VestingAccount::INIT_SPACE,
+        seeds = [b"vesting", miner.key().as_ref()],
+        bump,
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(mut)]
+    pub miner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        mut,
+        seeds = [b"solution", solution.miner.as_ref(), &solution.epoch.to_le_bytes()],
+        bump = solution.bump,
+        close = miner,
+    )]
+    pub solution: Account<'info, Solution>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", solution.miner.as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(
+        mut,
+        constraint = miner.key() == solution.miner @ ErrorCode::InvalidRecipient,
+    )]
+    pub miner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting", miner.key().as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// Token account to receive tokens. Miner signature is the authorization.
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = miner.key() == vesting.miner @ ErrorCode::Unauthorized
+    )]
+    pub miner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    /// Anyone can crank (permissionless)
+    pub crank: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.pending_authority != Pubkey::default() @ ErrorCode::NoPendingAuthority,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = new_authority.key() == mine_state.pending_authority @ ErrorCode::Unauthorized
+    )]
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AuditSupply<'info> {
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWordlistVersion<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateWordList<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = WORD_LIST_HEADER,
+        seeds = [b"word_list", authority.key().as_ref(), &id.to_le_bytes()],
+        bump,
+    )]
+    pub word_list: AccountLoader<'info, WordList>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendWordList<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::Unauthorized)]
+    pub word_list: AccountLoader<'info, WordList>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteWordList<'info> {
+    #[account(mut, has_one = authority @ ErrorCode::Unauthorized)]
+    pub word_list: AccountLoader<'info, WordList>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWordList<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// The sealed list to activate; omit to go back to the built-in list
+    pub word_list: Option<AccountLoader<'info, WordList>>,
+}
+
+#[derive(Accounts)]
+pub struct SetTextRules<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeSolution<'info> {
+    /// CHECK: May still have a pre-upgrade layout, so it is not deserialized. Owner and discriminator checked in handler.
+    #[account(mut)]
+    pub solution: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeState<'info> {
+    /// CHECK: May still have a pre-upgrade layout, so it is not deserialized. Address pinned by seeds, owner checked in handler.
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump,
+    )]
+    pub mine_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseExpired<'info> {
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        mut,
+        seeds = [b"solution", solution.miner.as_ref(), &solution.epoch.to_le_bytes()],
+        bump = solution.bump,
+        close = closer,
+    )]
     pub solution: Account<'info, Solution>,
 
+    /// Anyone can close expired solutions. Rent goes to caller as cleanup incentive.
+    #[account(mut)]
+    pub closer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFingerprintShard<'info> {
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        mut,
+        seeds = [b"fingerprints", &shard.epoch.to_le_bytes(), &[shard.block], &[shard.shard], &[shard.page]],
+        bump = shard.bump,
+        close = payer,
+    )]
+    pub shard: Account<'info, FingerprintShard>,
+
+    /// CHECK: Rent refund only; must be the shard's creator.
+    #[account(mut, address = shard.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAdminSet<'info> {
+    #[account(
+        mut,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AdminSet::INIT_SPACE,
+        seeds = [b"admin_set"],
+        bump,
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + AdminProposal::INIT_SPACE,
+        seeds = [b"proposal", &admin_set.proposal_count.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
     #[account(
-        mut,
-        seeds = [b"vesting", solution.miner.as_ref()],
-        bump = vesting.bump,
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
     )]
-    pub vesting: Account<'info, VestingAccount>,
+    pub admin_set: Account<'info, AdminSet>,
 
     #[account(
         mut,
-        constraint = miner.key() == solution.miner @ ErrorCode::InvalidRecipient,
+        seeds = [b"proposal", &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = proposal.generation == admin_set.generation @ ErrorCode::ProposalStale,
     )]
-    pub miner: Signer<'info>,
+    pub proposal: Account<'info, AdminProposal>,
 
-    pub system_program: Program<'info, System>,
+    pub member: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Withdraw<'info> {
+pub struct ExecuteAdminAction<'info> {
     #[account(
+        mut,
         seeds = [b"mine_state"],
         bump = mine_state.bump,
+        constraint = mine_state.crank_authority == admin_set.key() @ ErrorCode::Unauthorized,
     )]
     pub mine_state: Account<'info, MineState>,
 
     #[account(
         mut,
-        seeds = [b"vesting", miner.key().as_ref()],
-        bump = vesting.bump,
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
     )]
-    pub vesting: Account<'info, VestingAccount>,
+    pub admin_set: Account<'info, AdminSet>,
 
     #[account(
         mut,
-        seeds = [b"mint"],
-        bump,
+        seeds = [b"proposal", &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = proposal.generation == admin_set.generation @ ErrorCode::ProposalStale,
     )]
-    pub mint: Account<'info, Mint>,
+    pub proposal: Account<'info, AdminProposal>,
 
-    /// Token account to receive tokens. Miner signature is the authorization.
+    /// Anyone can execute once the threshold is met
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSetWordList<'info> {
     #[account(
         mut,
-        token::mint = mint,
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority == admin_set.key() @ ErrorCode::Unauthorized,
     )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub mine_state: Account<'info, MineState>,
 
     #[account(
-        constraint = miner.key() == vesting.miner @ ErrorCode::Unauthorized
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
     )]
-    pub miner: Signer<'info>,
+    pub admin_set: Account<'info, AdminSet>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"proposal", &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = proposal.generation == admin_set.generation @ ErrorCode::ProposalStale,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// Required unless the proposal clears the active list
+    pub word_list: Option<AccountLoader<'info, WordList>>,
+
+    /// Anyone can execute once the threshold is met
+    pub executor: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct AdvanceEpoch<'info> {
+pub struct ExecuteMigrateState<'info> {
     #[account(
         mut,
         seeds = [b"mine_state"],
         bump = mine_state.bump,
+        constraint = mine_state.crank_authority == admin_set.key() @ ErrorCode::Unauthorized,
     )]
     pub mine_state: Account<'info, MineState>,
 
-    /// Anyone can crank (permissionless)
-    pub crank: Signer<'info>,
+    #[account(
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = proposal.generation == admin_set.generation @ ErrorCode::ProposalStale,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// Anyone can execute once the threshold is met
+    pub executor: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct SetCrankAuthority<'info> {
+pub struct ExecuteCreateMetadata<'info> {
     #[account(
-        mut,
         seeds = [b"mine_state"],
         bump = mine_state.bump,
+        constraint = mine_state.crank_authority == admin_set.key() @ ErrorCode::Unauthorized,
     )]
     pub mine_state: Account<'info, MineState>,
 
     #[account(
-        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
     )]
-    pub authority: Signer<'info>,
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = proposal.generation == admin_set.generation @ ErrorCode::ProposalStale,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    #[account(
+        seeds = [b"mint"],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Created by Metaplex program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ResetState<'info> {
+pub struct ExecuteUpdateMetadata<'info> {
     #[account(
-        mut,
         seeds = [b"mine_state"],
         bump = mine_state.bump,
+        constraint = mine_state.crank_authority == admin_set.key() @ ErrorCode::Unauthorized,
     )]
     pub mine_state: Account<'info, MineState>,
 
     #[account(
-        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+        seeds = [b"admin_set"],
+        bump = admin_set.bump,
     )]
-    pub authority: Signer<'info>,
+    pub admin_set: Account<'info, AdminSet>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ ErrorCode::ProposalExecuted,
+        constraint = proposal.generation == admin_set.generation @ ErrorCode::ProposalStale,
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+
+    /// CHECK: Validated by Metaplex (update authority must be mine_state)
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Anyone can execute once the threshold is met
+    pub executor: Signer<'info>,
+
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct CloseExpired<'info> {
+pub struct PreviewVesting<'info> {
+    #[account(
+        seeds = [b"vesting", vesting.miner.as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+}
+
+#[derive(Accounts)]
+pub struct CurrentChallenge<'info> {
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+    )]
+    pub mine_state: Account<'info, MineState>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewClaim<'info> {
     #[account(
         seeds = [b"mine_state"],
         bump = mine_state.bump,
@@ -631,16 +2074,10 @@ pub struct CloseExpired<'info> {
     pub mine_state: Account<'info, MineState>,
 
     #[account(
-        mut,
         seeds = [b"solution", solution.miner.as_ref(), &solution.epoch.to_le_bytes()],
         bump = solution.bump,
-        close = closer,
     )]
     pub solution: Account<'info, Solution>,
-
-    /// Anyone can close expired solutions. Rent goes to caller as cleanup incentive.
-    #[account(mut)]
-    pub closer: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -648,6 +2085,7 @@ pub struct CreateMetadata<'info> {
     #[account(
         seeds = [b"mine_state"],
         bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
     )]
     pub mine_state: Account<'info, MineState>,
 
@@ -675,6 +2113,29 @@ pub struct CreateMetadata<'info> {
     pub token_metadata_program: UncheckedAccount<'info>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(
+        seeds = [b"mine_state"],
+        bump = mine_state.bump,
+        constraint = mine_state.crank_authority != Pubkey::default() @ ErrorCode::AuthorityRenounced,
+    )]
+    pub mine_state: Account<'info, MineState>,
+
+    /// CHECK: Validated by Metaplex (update authority must be mine_state)
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = authority.key() == mine_state.crank_authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
 // ============================================================
 // State
 // ============================================================
@@ -692,9 +2153,68 @@ pub struct MineState {
     pub settled_in_epoch: u64,     // 8   — reserved for compatibility
     pub total_supply: u64,         // 8   — committed supply (locked + unlocked + released)
     pub mint: Pubkey,              // 32
-    pub crank_authority: Pubkey,   // 32  — only this address can call advance_epoch
+    pub crank_authority: Pubkey,   // 32  — admin; Pubkey::default() once renounced
+    pub bump: u8,                  // 1
+    pub pending_authority: Pubkey, // 32  — proposed admin, Pubkey::default() if none
+    pub paused: u8,                // 1   — PAUSE_* bitmask, 0 = running
+    pub text_rules: PaddedTextRules, // 65 — verifier thresholds; len 0 = unset (default)
+    pub wordlist_version: u8,      // 1   — words::WORDLIST_V*; 0 = V1
+    pub word_list: Pubkey,         // 32  — active WordList for English; default = built-in list
+}                                  // total: 292 + 8 discriminator = 300
+
+impl MineState {
+    /// Text rules in force. An account resized from an older layout has
+    /// zeroed rules, which means "never set" and falls back to the default.
+    pub fn active_text_rules(&self) -> verify::TextRules {
+        match self.text_rules.rules() {
+            Some(rules) if self.text_rules.len != 0 => rules,
+            _ => verify::TextRules::DEFAULT,
+        }
+    }
+}
+
+/// `verify::TextRules` as Borsh bytes zero-padded to a fixed TEXT_RULES_PADDED_LEN,
+/// in `MineState` and in `AdminAction::SetTextRules` proposals. Neither layout
+/// moves when fields are appended to `TextRules`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct PaddedTextRules {
+    pub len: u8,                   // TextRules::SIZE when written
+    pub bytes: [u8; TEXT_RULES_PADDED_LEN],
+}
+
+impl PaddedTextRules {
+    pub fn new(rules: &verify::TextRules) -> Self {
+        let mut bytes = [0; TEXT_RULES_PADDED_LEN];
+        let encoded = rules.try_to_vec().unwrap();
+        bytes[..encoded.len()].copy_from_slice(&encoded);
+        PaddedTextRules { len: encoded.len() as u8, bytes }
+    }
+
+    /// The rules, or None if the bytes don't decode. Fields appended to
+    /// `TextRules` since they were written read as zero, which is "off".
+    pub fn rules(&self) -> Option<verify::TextRules> {
+        let len = self.len as usize;
+        if len > verify::TextRules::SIZE || self.bytes[len..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        verify::TextRules::deserialize(&mut &self.bytes[..]).ok()
+    }
+}
+
+/// Header of an on-chain wordlist. The words follow it in the account as
+/// zero-padded MAX_WORD_LEN-byte slots (`words::PackedWordlist`), up to
+/// WORD_LIST_CAPACITY of them.
+#[account(zero_copy)]
+pub struct WordList {
+    pub authority: Pubkey,         // 32  — writes and seals it
+    pub hash: [u8; 32],            // 32  — PackedWordlist::hash, set when sealed
+    pub id: u64,                   // 8
+    pub groups: [u16; 4],          // 8   — part-of-speech group sizes, set when sealed
+    pub count: u16,                // 2   — words written (highest slot + 1)
+    pub sealed: u8,                // 1   — 1 = immutable, can be activated
     pub bump: u8,                  // 1
-}                                  // total: 161 + 8 discriminator = 169
+    pub reserved: [u8; 4],         // 4
+}                                  // total: 88 + 8 discriminator = 96, then the word slots
 
 #[account]
 #[derive(InitSpace)]
@@ -705,7 +2225,22 @@ pub struct Solution {
     pub nonce: u64,                // 8
     pub hash: [u8; 32],            // 32
     pub bump: u8,                  // 1
-}                                  // total: 113 + 8 discriminator = 121
+    pub fingerprint: u64,          // 8   — verify::simhash of the text; 0 = not recorded
+}                                  // total: 121 + 8 discriminator = 129
+
+/// One page of a shard of an epoch's SimHash index (see `record_fingerprint`).
+#[account]
+#[derive(InitSpace)]
+pub struct FingerprintShard {
+    pub epoch: u64,                // 8
+    pub block: u8,                 // 1   — fingerprint block this shard indexes
+    pub shard: u8,                 // 1   — block bits % fingerprint_shards
+    pub page: u8,                  // 1   — position in the shard's chain of pages
+    pub payer: Pubkey,             // 32  — created it; gets the rent back
+    pub bump: u8,                  // 1
+    #[max_len(FINGERPRINTS_PER_SHARD)]
+    pub fingerprints: Vec<u64>,    // 4 + 8 × 128
+}                                  // total: 1072 + 8 discriminator = 1080
 
 #[account]
 #[derive(InitSpace)]
@@ -717,6 +2252,130 @@ pub struct VestingAccount {
     pub bump: u8,                  // 1
 }                                  // total: 57 + 8 discriminator = 65
 
+#[account]
+#[derive(InitSpace)]
+pub struct AdminSet {
+    #[max_len(MAX_ADMINS)]
+    pub members: Vec<Pubkey>,      // 4 + 32 × 10
+    pub threshold: u8,             // 1   — approvals required to execute
+    pub generation: u64,           // 8   — bumped on membership change; stales old proposals
+    pub proposal_count: u64,       // 8   — next proposal id
+    pub bump: u8,                  // 1
+}
+
+impl AdminSet {
+    /// Position of `key` in `members`, used as its approval bit.
+    pub fn member_index(&self, key: &Pubkey) -> Result<usize> {
+        self.members
+            .iter()
+            .position(|m| m == key)
+            .ok_or_else(|| error!(ErrorCode::NotAdmin))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AdminProposal {
+    pub id: u64,                   // 8
+    pub generation: u64,           // 8   — AdminSet generation at proposal time
+    pub proposer: Pubkey,          // 32
+    pub action: AdminAction,
+    pub approvals: u16,            // 2   — bit i set = members[i] approved
+    pub executed: bool,            // 1
+    pub bump: u8,                  // 1
+}
+
+/// Admin actions that require M-of-N approval once an AdminSet exists.
+///
+/// The Borsh tag is the variant index and pending proposals are stored with
+/// it, so new variants go at the end; reordering would execute an old
+/// proposal as a different action.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub enum AdminAction {
+    MigrateState,
+    ProposeAuthority { new_authority: Pubkey },
+    RenounceAuthority,
+    UpdateAdminSet {
+        #[max_len(MAX_ADMINS)]
+        members: Vec<Pubkey>,
+        threshold: u8,
+    },
+    CreateMetadata {
+        #[max_len(32)]
+        name: String,
+        #[max_len(10)]
+        symbol: String,
+        #[max_len(200)]
+        uri: String,
+    },
+    SetPause { paused: u8 },
+    UpdateMetadata {
+        #[max_len(32)]
+        name: String,
+        #[max_len(10)]
+        symbol: String,
+        #[max_len(200)]
+        uri: String,
+    },
+    FinalizeMetadata,
+    SetTextRules { rules: PaddedTextRules },
+    SetWordlistVersion { version: u8 },
+    SetWordList { word_list: Pubkey, hash: [u8; 32] },
+}
+
+// ============================================================
+// View Return Data
+// ============================================================
+
+/// Returned by `preview_vesting`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VestingPreview {
+    pub locked: u64,               // still locked at the current clock
+    pub unlocked: u64,             // already dripped on-chain
+    pub claimable: u64,            // what `withdraw` would mint right now
+    pub fully_vested_at: i64,      // when `locked` reaches zero absent new claims
+}
+
+/// Returned by `current_challenge`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ChallengeInfo {
+    pub epoch_number: u64,
+    pub difficulty: u64,
+    pub challenge_seed: [u8; 32],
+    pub required_words: Vec<String>, // in the order they must appear; empty for other challenge kinds
+    pub epoch_end_time: i64,
+    pub seconds_left: i64,         // 0 once the epoch has ended
+    pub paused: u8,                // PAUSE_* bitmask
+    pub text_rules: verify::TextRules, // thresholds submit_solution will apply (difficulty-scaled)
+    pub language: String,          // ISO 639-1 code of this epoch's language pack
+    pub challenge_kind: u8,        // challenge::KIND_* bit picked this epoch
+    pub challenge: String,         // what the text must do, in words
+    pub wordlist_version: u8,      // words::WORDLIST_V* in force; 0 = V1
+    pub word_list: Pubkey,         // active WordList (pass it to submit_solution); default = none
+}
+
+/// Returned by `preview_claim`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimPreview {
+    pub epoch_over: bool,          // solution's epoch has ended
+    pub expired: bool,             // past CLAIM_EXPIRY_EPOCHS
+    pub expires_at_epoch: u64,
+    pub reward: u64,               // reward `claim` would add to vesting now
+}
+
+// ============================================================
+// Events
+// ============================================================
+
+/// Emitted by `audit_supply`.
+#[event]
+pub struct SupplyAudit {
+    pub mint_supply: u64,          // tokens actually minted
+    pub total_supply: u64,         // committed (minted + locked + unlocked)
+    pub max_supply: u64,
+    pub unminted_committed: u64,   // total_supply - mint_supply (still vesting)
+}
+
 // ============================================================
 // Errors
 // ============================================================
@@ -724,7 +2383,7 @@ pub struct VestingAccount {
 #[error_code]
 pub enum ErrorCode {
     #[msg("Text verification failed")]
-    InvalidText,
+    InvalidText,                   // superseded by the Text* codes below; kept so error numbers stay stable
     #[msg("Hash does not meet difficulty requirement")]
     InsufficientDifficulty,
     #[msg("Maximum token supply reached")]
@@ -743,4 +2402,521 @@ pub enum ErrorCode {
     Unauthorized,
     #[msg("Nothing to withdraw")]
     NothingToWithdraw,
+    #[msg("Authority has been renounced")]
+    AuthorityRenounced,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Invalid admin set: 1-10 unique members and 1 <= threshold <= members")]
+    InvalidAdminSet,
+    #[msg("Signer is not a member of the admin set")]
+    NotAdmin,
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    #[msg("Proposal has already been executed")]
+    ProposalExecuted,
+    #[msg("Proposal was made under a previous admin set")]
+    ProposalStale,
+    #[msg("Proposal action does not match this instruction")]
+    ProposalActionMismatch,
+    #[msg("Vesting accounts must be program-owned and sorted by address")]
+    InvalidVestingAccount,
+    #[msg("Supply invariant violated: minted > committed or committed > maximum supply")]
+    SupplyInvariantViolated,
+    #[msg("This instruction is paused")]
+    Paused,
+    #[msg("Unknown pause flag bits")]
+    InvalidPauseFlags,
+    #[msg("Text length out of bounds")]
+    TextLength,
+    #[msg("Text has characters outside the language pack")]
+    TextNonAscii,
+    #[msg("A sentence has too few or too many words")]
+    TextSentenceWordCount,
+    #[msg("Text contains a duplicate sentence")]
+    TextDuplicateSentence,
+    #[msg("Text has too few sentences")]
+    TextTooFewSentences,
+    #[msg("Text has too few questions")]
+    TextMissingQuestion,
+    #[msg("Text needs a short sentence")]
+    TextMissingShortSentence,
+    #[msg("Text needs a long sentence")]
+    TextMissingLongSentence,
+    #[msg("Vowel ratio out of bounds")]
+    TextVowelRatio,
+    #[msg("Space ratio out of bounds")]
+    TextSpaceRatio,
+    #[msg("Consonant clusters too long")]
+    TextConsonantCluster,
+    #[msg("Too few of the language's common bigrams")]
+    TextBigram,
+    #[msg("Text needs more distinct byte values")]
+    TextByteDiversity,
+    #[msg("Required word missing, out of order, or too close to the previous one")]
+    TextMissingRequiredWord,
+    #[msg("Invalid text rules")]
+    InvalidTextRules,
+    #[msg("Sentences must start with a capital letter and end with . ! or ?")]
+    TextSentenceStructure,
+    #[msg("Letter frequencies too far from the language model")]
+    TextLetterFrequency,
+    #[msg("Too few common trigrams for the language")]
+    TextTrigramFrequency,
+    #[msg("Too few dictionary words")]
+    TextDictionaryWords,
+    #[msg("Too many repeated phrases")]
+    TextRepeatedPhrases,
+    #[msg("Text is a near-duplicate of an earlier solution this epoch")]
+    NearDuplicate,
+    #[msg("Missing or wrong fingerprint shard account")]
+    InvalidFingerprintShard,
+    #[msg("Sentence initials don't spell the challenge word")]
+    ChallengeAcrostic,
+    #[msg("No sentence starts with the challenge word")]
+    ChallengeSentenceStart,
+    #[msg("Challenge word not found in a question")]
+    ChallengeQuestionWord,
+    #[msg("Wrong number of sentences (even/odd) for the challenge")]
+    ChallengeSentenceParity,
+    #[msg("Too few words from the challenge category")]
+    ChallengeCategory,
+    #[msg("Unknown wordlist version")]
+    InvalidWordlistVersion,
+    #[msg("Invalid word list: bad word, group sizes, or past its capacity")]
+    InvalidWordList,
+    #[msg("Word list is sealed")]
+    WordListSealed,
+    #[msg("Word list is not sealed")]
+    WordListNotSealed,
+    #[msg("Word list does not match the expected hash")]
+    WordListHashMismatch,
+    #[msg("The active word list must be passed (first remaining account)")]
+    MissingWordList,
+    #[msg("Near-duplicate index shard has used all 256 pages for this epoch")]
+    FingerprintShardFull,
+}
+
+impl From<verify::TextRejection> for ErrorCode {
+    fn from(r: verify::TextRejection) -> Self {
+        use verify::TextRejection::*;
+        match r {
+            Length => ErrorCode::TextLength,
+            NonAscii => ErrorCode::TextNonAscii,
+            SentenceWordCount => ErrorCode::TextSentenceWordCount,
+            SentenceStructure => ErrorCode::TextSentenceStructure,
+            DuplicateSentence => ErrorCode::TextDuplicateSentence,
+            TooFewSentences => ErrorCode::TextTooFewSentences,
+            MissingQuestion => ErrorCode::TextMissingQuestion,
+            MissingShortSentence => ErrorCode::TextMissingShortSentence,
+            MissingLongSentence => ErrorCode::TextMissingLongSentence,
+            VowelRatio => ErrorCode::TextVowelRatio,
+            SpaceRatio => ErrorCode::TextSpaceRatio,
+            ConsonantCluster => ErrorCode::TextConsonantCluster,
+            Bigram => ErrorCode::TextBigram,
+            ByteDiversity => ErrorCode::TextByteDiversity,
+            LetterFrequency => ErrorCode::TextLetterFrequency,
+            TrigramFrequency => ErrorCode::TextTrigramFrequency,
+            DictionaryWords => ErrorCode::TextDictionaryWords,
+            RepeatedPhrases => ErrorCode::TextRepeatedPhrases,
+            MissingRequiredWord(_) => ErrorCode::TextMissingRequiredWord,
+        }
+    }
+}
+
+impl From<challenge::ChallengeRejection> for ErrorCode {
+    fn from(r: challenge::ChallengeRejection) -> Self {
+        use challenge::ChallengeRejection::*;
+        match r {
+            Acrostic => ErrorCode::ChallengeAcrostic,
+            SentenceStart => ErrorCode::ChallengeSentenceStart,
+            QuestionWord => ErrorCode::ChallengeQuestionWord,
+            SentenceParity => ErrorCode::ChallengeSentenceParity,
+            Category => ErrorCode::ChallengeCategory,
+        }
+    }
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poi_core::mining::{HALVING_INTERVAL, INITIAL_REWARD};
+
+    fn test_state() -> MineState {
+        MineState {
+            total_mined: 0,
+            difficulty: INITIAL_DIFFICULTY,
+            challenge_seed: [7; 32],
+            epoch_number: 0,
+            epoch_start_time: 0,
+            epoch_end_time: EPOCH_DURATION,
+            solutions_in_epoch: 0,
+            settled_in_epoch: 0,
+            total_supply: 0,
+            mint: Pubkey::new_unique(),
+            crank_authority: Pubkey::new_unique(),
+            bump: 255,
+            pending_authority: Pubkey::default(),
+            paused: 0,
+            text_rules: PaddedTextRules::new(&verify::TextRules::DEFAULT),
+            wordlist_version: words::WORDLIST_V1,
+            word_list: Pubkey::default(),
+        }
+    }
+
+    fn test_vesting() -> VestingAccount {
+        VestingAccount { miner: Pubkey::new_unique(), locked: 0, unlocked: 0, last_update: 0, bump: 255 }
+    }
+
+    fn clock_at(ts: i64) -> Clock {
+        Clock { slot: ts as u64 * 2, unix_timestamp: ts, ..Clock::default() }
+    }
+
+    /// Minted + still-vesting must never exceed MAX_SUPPLY or committed supply.
+    fn assert_cap(state: &MineState, minted: u64, v: &VestingAccount) {
+        let outstanding = minted + v.locked + v.unlocked;
+        assert!(outstanding <= state.total_supply, "outstanding {} > committed {}", outstanding, state.total_supply);
+        assert!(state.total_supply <= MAX_SUPPLY, "committed {} > MAX_SUPPLY", state.total_supply);
+    }
+
+    #[test]
+    fn test_migrate_keeps_supply_and_halving() {
+        let mut state = test_state();
+        state.total_mined = HALVING_INTERVAL + 5;
+        state.total_supply = 1_000_000;
+        state.difficulty = 17;
+        state.epoch_number = 42;
+        let seed_before = state.challenge_seed;
+
+        migrate_mine_state(&mut state, &Pubkey::new_unique(), &clock_at(1_000), 400_000, 100_000).unwrap();
+
+        assert_eq!(state.total_supply, 1_000_000);
+        assert_eq!(state.total_mined, HALVING_INTERVAL + 5);
+        assert_eq!(state.difficulty, 17);
+        assert_eq!(state.epoch_number, 43);
+        assert_eq!(state.epoch_end_time, 1_000 + EPOCH_DURATION);
+        assert_ne!(state.challenge_seed, seed_before);
+    }
+
+    #[test]
+    fn test_migrate_recovers_zeroed_supply() {
+        // A legacy reset_state zeroed total_supply while tokens were minted / vesting
+        let mut state = test_state();
+        migrate_mine_state(&mut state, &Pubkey::new_unique(), &clock_at(1), 3_000, 2_000).unwrap();
+        assert_eq!(state.total_supply, 5_000);
+    }
+
+    #[test]
+    fn test_migrate_rejects_over_cap() {
+        let mut state = test_state();
+        assert!(migrate_mine_state(&mut state, &Pubkey::new_unique(), &clock_at(1), MAX_SUPPLY, 1).is_err());
+    }
+
+    #[test]
+    fn test_supply_invariant() {
+        assert!(check_supply_invariant(0, 0).is_ok());
+        assert!(check_supply_invariant(500, 1_000).is_ok());
+        assert!(check_supply_invariant(MAX_SUPPLY, MAX_SUPPLY).is_ok());
+        assert!(check_supply_invariant(1_001, 1_000).is_err());
+        assert!(check_supply_invariant(0, MAX_SUPPLY + 1).is_err());
+    }
+
+    #[test]
+    fn test_cap_holds_across_migrations() {
+        let mut state = test_state();
+        let mut v = test_vesting();
+        let state_key = Pubkey::new_unique();
+
+        // Start a few rewards short of the cap, with everything so far already minted
+        state.total_supply = MAX_SUPPLY - 3 * INITIAL_REWARD - 1_234;
+        let mut minted = state.total_supply;
+
+        let mut now: i64 = 0;
+        for round in 0..12 {
+            now += VESTING_DURATION / 4;
+
+            // claim
+            let reward = reserve_reward(&mut state);
+            drip_vesting(&mut v, now);
+            v.locked += reward;
+            assert_cap(&state, minted, &v);
+
+            // withdraw
+            drip_vesting(&mut v, now);
+            minted += v.unlocked;
+            v.unlocked = 0;
+            assert_cap(&state, minted, &v);
+
+            // migrate every other round, recomputing from mint + vesting
+            if round % 2 == 1 {
+                migrate_mine_state(&mut state, &state_key, &clock_at(now), minted, v.locked + v.unlocked).unwrap();
+                assert_cap(&state, minted, &v);
+            }
+        }
+
+        assert_eq!(state.total_supply, MAX_SUPPLY);
+        assert_eq!(reserve_reward(&mut state), 0);
+    }
+
+    #[test]
+    fn test_cap_holds_after_legacy_reset() {
+        // Old reset_state zeroed counters while nearly the whole supply was out
+        let mut state = test_state();
+        let mut v = test_vesting();
+        let minted = MAX_SUPPLY - INITIAL_REWARD / 2;
+        v.locked = INITIAL_REWARD / 4;
+
+        migrate_mine_state(&mut state, &Pubkey::new_unique(), &clock_at(1), minted, v.locked).unwrap();
+        assert_cap(&state, minted, &v);
+
+        for _ in 0..4 {
+            v.locked += reserve_reward(&mut state);
+            assert_cap(&state, minted, &v);
+        }
+        assert_eq!(minted + v.locked, MAX_SUPPLY);
+    }
+
+    #[test]
+    fn test_stored_text_rules_layout() {
+        // Fields after text_rules stay put as TextRules grows
+        let mut state = test_state();
+        assert_eq!(state.try_to_vec().unwrap().len(), MineState::INIT_SPACE);
+        assert_eq!(8 + MineState::INIT_SPACE, 300);
+        state.wordlist_version = 0xAB;
+        let data = state.try_to_vec().unwrap();
+        assert_eq!(PaddedTextRules::INIT_SPACE, 65);
+        assert_eq!(data[267 - 8], 0xAB); // account offset 267, after the discriminator
+        assert_eq!(state.active_text_rules(), verify::TextRules::DEFAULT);
+
+        // Zeroed by resize_state: unset
+        state.text_rules = PaddedTextRules { len: 0, bytes: [0; TEXT_RULES_PADDED_LEN] };
+        assert_eq!(state.active_text_rules(), verify::TextRules::DEFAULT);
+        state.text_rules = PaddedTextRules::new(&verify::TextRules::PUBLISHED);
+        assert_eq!(state.active_text_rules(), verify::TextRules::PUBLISHED);
+    }
+
+    #[test]
+    fn test_withdraw_while_paused() {
+        // Every other pause bit leaves withdraw alone
+        let mut state = test_state();
+        let mut v = VestingAccount { locked: 1_000, ..test_vesting() };
+        state.paused = PAUSE_ALL & !PAUSE_WITHDRAW_UNLOCKED;
+        assert_eq!(take_withdrawable(&state, &mut v, VESTING_DURATION / 4).unwrap(), 250);
+        assert_eq!((v.locked, v.unlocked, v.last_update), (750, 0, VESTING_DURATION / 4));
+        assert!(take_withdrawable(&state, &mut v, VESTING_DURATION / 4).is_err());
+
+        state.paused = PAUSE_WITHDRAW_UNLOCKED;
+        assert!(take_withdrawable(&state, &mut v, VESTING_DURATION).is_err());
+        assert_eq!((v.locked, v.last_update), (750, VESTING_DURATION / 4));
+        // Released on the first withdraw after unpausing
+        state.paused = 0;
+        assert_eq!(take_withdrawable(&state, &mut v, 2 * VESTING_DURATION).unwrap(), 750);
+    }
+
+    #[test]
+    fn test_admin_action_tags() {
+        // Stored proposals decode by tag: existing variants keep their index
+        let tag = |action: AdminAction| action.try_to_vec().unwrap()[0];
+        assert_eq!(tag(AdminAction::MigrateState), 0);
+        assert_eq!(tag(AdminAction::RenounceAuthority), 2);
+        assert_eq!(tag(AdminAction::UpdateAdminSet { members: vec![], threshold: 1 }), 3);
+        let metadata = || (String::new(), String::new(), String::new());
+        let (name, symbol, uri) = metadata();
+        assert_eq!(tag(AdminAction::CreateMetadata { name, symbol, uri }), 4);
+        assert_eq!(tag(AdminAction::SetPause { paused: PAUSE_ALL }), 5);
+        let (name, symbol, uri) = metadata();
+        assert_eq!(tag(AdminAction::UpdateMetadata { name, symbol, uri }), 6);
+        assert_eq!(tag(AdminAction::FinalizeMetadata), 7);
+        let rules = PaddedTextRules::new(&verify::TextRules::DEFAULT);
+        assert_eq!(tag(AdminAction::SetTextRules { rules }), 8);
+        assert_eq!(tag(AdminAction::SetWordlistVersion { version: 1 }), 9);
+        let (word_list, hash) = (Pubkey::default(), [0; 32]);
+        assert_eq!(tag(AdminAction::SetWordList { word_list, hash }), 10);
+    }
+
+    #[test]
+    fn test_proposed_text_rules() {
+        let published = PaddedTextRules::new(&verify::TextRules::PUBLISHED);
+        assert_eq!(published.try_to_vec().unwrap().len(), PaddedTextRules::INIT_SPACE);
+        assert_eq!(published.rules(), Some(verify::TextRules::PUBLISHED));
+
+        // Proposed before the last fields existed: they decode as zero (off)
+        let mut older = published;
+        older.len -= 2;
+        older.bytes[older.len as usize..].fill(0);
+        let rules = older.rules().unwrap();
+        assert_eq!((rules.word_derivation, rules.extra_words), (0, 0));
+        assert_eq!(rules.max_letter_chi2, verify::TextRules::PUBLISHED.max_letter_chi2);
+
+        // Garbage past `len`, or a layout newer than this program
+        let mut bad = published;
+        bad.bytes[TEXT_RULES_PADDED_LEN - 1] = 1;
+        assert!(bad.rules().is_none());
+        let mut newer = published;
+        newer.len += 1;
+        assert!(newer.rules().is_none());
+    }
+
+    /// Spread-out fingerprints (about 32 bits apart), the first being 0.
+    fn spread(i: u64) -> u64 {
+        i.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Page `page` of shard 0 of block 0, as `record_fingerprint` finds it on-chain.
+    fn fingerprint_page(epoch: u64, page: u8, fingerprints: Vec<u64>) -> (Pubkey, Vec<u8>) {
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"fingerprints", &epoch.to_le_bytes(), &[0], &[0], &[page]],
+            &crate::ID,
+        );
+        let shard = FingerprintShard { epoch, block: 0, shard: 0, page, payer: Pubkey::new_unique(), bump, fingerprints };
+        let mut data = Vec::new();
+        shard.try_serialize(&mut data).unwrap();
+        data.resize(8 + FingerprintShard::INIT_SPACE, 0);
+        (key, data)
+    }
+
+    #[test]
+    fn test_full_fingerprint_page() {
+        let mut page = FingerprintShard {
+            epoch: 0,
+            block: 0,
+            shard: 0,
+            page: 0,
+            payer: Pubkey::new_unique(),
+            bump: 255,
+            fingerprints: Vec::new(),
+        };
+        for i in 0..FINGERPRINTS_PER_SHARD as u64 {
+            assert!(index_fingerprint(&mut page, spread(i), 3).unwrap());
+        }
+        assert_eq!(page.try_to_vec().unwrap().len(), FingerprintShard::INIT_SPACE);
+        // Full: still checked, but a fresh fingerprint goes on the next page
+        assert_eq!(index_fingerprint(&mut page, 0x10, 3).unwrap_err(), error!(ErrorCode::NearDuplicate));
+        let fresh = spread(FINGERPRINTS_PER_SHARD as u64);
+        assert!(page.fingerprints.iter().all(|&f| verify::hamming_distance(f, fresh) > 3));
+        assert!(!index_fingerprint(&mut page, fresh, 3).unwrap());
+        assert_eq!(page.fingerprints.len(), FINGERPRINTS_PER_SHARD);
+    }
+
+    #[test]
+    fn test_fingerprint_page_chain() {
+        // One block with one shard, so every fingerprint lands in the same chain
+        let rules = verify::TextRules { fingerprint_shards: 1, near_duplicate_bits: 0, ..verify::TextRules::DEFAULT };
+        let (key0, mut data0) = fingerprint_page(5, 0, (0..FINGERPRINTS_PER_SHARD as u64).map(spread).collect());
+        let (key1, mut data1) = fingerprint_page(5, 1, Vec::new());
+        let (payer_key, system_key) = (Pubkey::new_unique(), system_program::ID);
+        let (mut lamports0, mut lamports1, mut payer_lamports, mut system_lamports) = (1, 1, 1, 1);
+        let (mut payer_data, mut system_data) = ([0u8; 0], [0u8; 0]);
+        let pages = [
+            AccountInfo::new(&key0, false, true, &mut lamports0, &mut data0, &crate::ID, false, 0),
+            AccountInfo::new(&key1, false, true, &mut lamports1, &mut data1, &crate::ID, false, 0),
+        ];
+        let payer = AccountInfo::new(&payer_key, true, true, &mut payer_lamports, &mut payer_data, &system_key, false, 0);
+        let system = AccountInfo::new(&system_key, false, false, &mut system_lamports, &mut system_data, &system_key, true, 0);
+
+        // The 129th distinct fingerprint is accepted, on page 1
+        let fresh = spread(FINGERPRINTS_PER_SHARD as u64);
+        record_fingerprint(&pages, &payer, &system, 5, fresh, &rules).unwrap();
+        let page1 = FingerprintShard::try_deserialize(&mut &pages[1].try_borrow_data().unwrap()[..]).unwrap();
+        assert_eq!(page1.fingerprints, vec![fresh]);
+
+        // Every page is checked, full or not
+        assert_eq!(record_fingerprint(&pages, &payer, &system, 5, spread(3), &rules).unwrap_err(), error!(ErrorCode::NearDuplicate));
+        assert_eq!(record_fingerprint(&pages, &payer, &system, 5, fresh, &rules).unwrap_err(), error!(ErrorCode::NearDuplicate));
+
+        // A full page can't end the chain, and none can be skipped
+        let other = spread(FINGERPRINTS_PER_SHARD as u64 + 1);
+        assert_eq!(record_fingerprint(&pages[..1], &payer, &system, 5, other, &rules).unwrap_err(), error!(ErrorCode::InvalidFingerprintShard));
+        assert_eq!(record_fingerprint(&pages[1..], &payer, &system, 5, other, &rules).unwrap_err(), error!(ErrorCode::InvalidFingerprintShard));
+        record_fingerprint(&pages, &payer, &system, 5, other, &rules).unwrap();
+    }
+
+    #[test]
+    fn test_vesting_preview() {
+        let v = VestingAccount { locked: 1_000, unlocked: 40, last_update: 100, ..test_vesting() };
+        let now = 100 + VESTING_DURATION / 4;
+        let preview = vesting_preview(&v, now);
+        assert_eq!((preview.locked, preview.unlocked, preview.claimable), (750, 40, 290));
+        assert_eq!(preview.fully_vested_at, 100 + VESTING_DURATION);
+        // What withdraw would take at the same clock
+        assert_eq!(take_withdrawable(&test_state(), &mut v.clone(), now).unwrap(), preview.claimable);
+
+        let preview = vesting_preview(&v, 100 + 2 * VESTING_DURATION);
+        assert_eq!((preview.locked, preview.claimable), (0, 1_040));
+        let empty = VestingAccount { last_update: 100, ..test_vesting() };
+        assert_eq!(vesting_preview(&empty, 500).fully_vested_at, 100);
+    }
+
+    #[test]
+    fn test_challenge_info() {
+        let mut state = test_state();
+        state.epoch_end_time = 1_000;
+        let info = challenge_info(&state, None, 400);
+        assert_eq!(info.seconds_left, 600);
+        assert_eq!(challenge_info(&state, None, 1_500).seconds_left, 0);
+        assert_eq!(info.text_rules, state.active_text_rules().for_difficulty(state.difficulty));
+        assert_eq!((info.language.as_str(), info.challenge_kind), ("en", challenge::KIND_REQUIRED_WORDS));
+        let rw = words::derive_words(&state.challenge_seed, state.difficulty);
+        let expected: Vec<String> =
+            (0..rw.count).map(|i| String::from_utf8(rw.words[i][..rw.lens[i]].to_vec()).unwrap()).collect();
+        assert_eq!(info.required_words, expected);
+
+        // The language pack follows the rules' mask
+        let mut rules = verify::TextRules::DEFAULT;
+        rules.languages = lang::LANG_ES;
+        state.text_rules = PaddedTextRules::new(&rules);
+        let info = challenge_info(&state, None, 400);
+        assert_eq!(info.language, "es");
+        assert!(info.required_words.iter().all(|w| lang::WORDLIST_ES.contains(&w.as_str())));
+
+        // seed[30] picks the kind among those enabled
+        rules.languages = 0;
+        rules.challenge_kinds = challenge::KIND_REQUIRED_WORDS | challenge::KIND_SENTENCE_PARITY;
+        state.text_rules = PaddedTextRules::new(&rules);
+        state.challenge_seed[30] = 1;
+        let info = challenge_info(&state, None, 400);
+        assert_eq!(info.challenge_kind, challenge::KIND_SENTENCE_PARITY);
+        assert!(info.required_words.is_empty());
+        assert!(info.challenge.contains("number of sentences"));
+
+        // An active WordList replaces the English list
+        state.text_rules = PaddedTextRules::new(&verify::TextRules::DEFAULT);
+        state.word_list = Pubkey::new_unique();
+        let list = ["apple", "build", "quiet", "often"];
+        let mut slots = vec![0u8; list.len() * words::MAX_WORD_LEN];
+        for (slot, word) in slots.chunks_exact_mut(words::MAX_WORD_LEN).zip(list) {
+            slot[..word.len()].copy_from_slice(word.as_bytes());
+        }
+        let packed = words::PackedWordlist::new(&slots, [1, 1, 1, 1]);
+        assert!(packed.is_valid());
+        let info = challenge_info(&state, Some(&packed), 400);
+        assert_eq!(info.required_words.len(), expected.len());
+        assert!(info.required_words.iter().all(|w| list.contains(&w.as_str())));
+        assert_eq!(info.word_list, state.word_list);
+    }
+
+    #[test]
+    fn test_claim_preview() {
+        let mut state = test_state();
+        state.epoch_number = 10;
+        state.epoch_end_time = 1_000;
+
+        // The current epoch is over once its end time passes; a past one always is
+        assert!(!claim_preview(&state, 10, 999).epoch_over);
+        assert!(claim_preview(&state, 10, 1_000).epoch_over);
+        assert!(!claim_preview(&state, 11, 5_000).epoch_over);
+        let preview = claim_preview(&state, 9, 0);
+        assert!(preview.epoch_over && !preview.expired);
+        assert_eq!(preview.expires_at_epoch, 9 + CLAIM_EXPIRY_EPOCHS);
+        assert_eq!(preview.reward, INITIAL_REWARD);
+
+        state.epoch_number = 9 + CLAIM_EXPIRY_EPOCHS;
+        assert!(claim_preview(&state, 9, 0).expired);
+
+        // Capped by MAX_SUPPLY, as claim would be
+        state.total_supply = MAX_SUPPLY - 5;
+        assert_eq!(claim_preview(&state, 9, 0).reward, 5);
+        assert_eq!(reserve_reward(&mut state.clone()), 5);
+    }
 }
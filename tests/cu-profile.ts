@@ -0,0 +1,181 @@
+/**
+ * Compute-unit profile of submit_solution over tests/vectors/cu_corpus.json.
+ *
+ * Each corpus text is filled in with the epoch's required words, mined and
+ * submitted by a fresh miner; the units consumed are compared with
+ * tests/cu-baseline.json. Fails when a text costs more than its baseline plus
+ * the tolerance, or more than the compute limit the reference miner requests,
+ * and when the baseline has no entry for a text.
+ *
+ * UPDATE_CU_BASELINE=1 records the measured values instead. Built with
+ * `--features cu-log`, the program also logs remaining units around text
+ * verification and the PoW hash, and the table shows that breakdown.
+ */
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { ComputeBudgetProgram, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { expect } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+import { keccak256 } from "js-sha3";
+import { Poi } from "../target/types/poi";
+import corpus from "./vectors/cu_corpus.json";
+import vectors from "./vectors/text_rules.json";
+
+const BASELINE_PATH = path.join(__dirname, "cu-baseline.json");
+const UPDATE = process.env.UPDATE_CU_BASELINE === "1";
+
+interface Baseline {
+  tolerance_pct: number;
+  max_units: number;                          // compute limit miner/mainnet-miner.ts requests
+  submit_solution: Record<string, number>;    // corpus text name → units
+}
+
+// snake_case rule set from text_rules.json → the IDL's camelCase TextRules
+function toTextRules(fields: Record<string, number | boolean>): any {
+  const rules: Record<string, number | boolean> = {};
+  for (const [k, v] of Object.entries(fields)) {
+    rules[k.replace(/_([a-z])/g, (_, c: string) => c.toUpperCase())] = v;
+  }
+  return rules;
+}
+
+// Same check as check_difficulty: `difficulty` leading zero bits
+function checkDifficulty(hash: Buffer, difficulty: number): boolean {
+  const fullBytes = Math.floor(difficulty / 8);
+  const remainingBits = difficulty % 8;
+  for (let i = 0; i < fullBytes; i++) {
+    if (hash[i] !== 0) return false;
+  }
+  if (remainingBits > 0 && fullBytes < 32) {
+    const mask = 0xff << (8 - remainingBits);
+    if ((hash[fullBytes]! & mask) !== 0) return false;
+  }
+  return true;
+}
+
+function grindNonce(seed: Buffer, miner: PublicKey, text: Buffer, difficulty: number): bigint {
+  const nonceBuf = Buffer.alloc(8);
+  for (let nonce = 0n; ; nonce++) {
+    nonceBuf.writeBigUInt64LE(nonce);
+    const hash = Buffer.from(keccak256(Buffer.concat([seed, miner.toBuffer(), text, Buffer.from("||"), nonceBuf])), "hex");
+    if (checkDifficulty(hash, difficulty)) return nonce;
+  }
+}
+
+// "Program consumption: N units remaining" lines from sol_log_compute_units (cu-log builds)
+function remainingUnits(logs: string[]): number[] {
+  return logs
+    .map((l) => /Program consumption: (\d+) units remaining/.exec(l))
+    .filter((m): m is RegExpExecArray => m !== null)
+    .map((m) => Number(m[1]));
+}
+
+describe("cu-profile", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.Poi as Program<Poi>;
+  const conn = provider.connection;
+
+  const [stateAddr] = PublicKey.findProgramAddressSync([Buffer.from("mine_state")], program.programId);
+  const [mintAddr] = PublicKey.findProgramAddressSync([Buffer.from("mint")], program.programId);
+
+  const baseline: Baseline = JSON.parse(fs.readFileSync(BASELINE_PATH, "utf-8"));
+  const measured: Record<string, number> = {};
+
+  before(async () => {
+    if (!(await conn.getAccountInfo(stateAddr))) {
+      await program.methods
+        .initialize()
+        .accountsPartial({
+          mineState: stateAddr,
+          mint: mintAddr,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+    }
+  });
+
+  after(async () => {
+    await setRules("default");
+    if (UPDATE) {
+      baseline.submit_solution = measured;
+      fs.writeFileSync(BASELINE_PATH, JSON.stringify(baseline, null, 2) + "\n");
+      console.log(`      wrote ${BASELINE_PATH}`);
+    }
+  });
+
+  async function setRules(name: string) {
+    const fields = (vectors.rule_sets as Record<string, Record<string, number | boolean>>)[name];
+    if (!fields) throw new Error(`unknown rule set ${name}`);
+    await program.methods
+      .setTextRules(toTextRules(fields))
+      .accountsPartial({ mineState: stateAddr, authority: provider.wallet.publicKey })
+      .rpc();
+  }
+
+  it("baseline covers the corpus", function () {
+    if (UPDATE) this.skip();
+    const names = corpus.texts.map(entry => entry.name).sort();
+    expect(Object.keys(baseline.submit_solution).sort(), "stale tests/cu-baseline.json; run yarn cu-baseline")
+      .to.deep.equal(names);
+  });
+
+  for (const entry of corpus.texts) {
+    it(`submit_solution: ${entry.name}`, async () => {
+      await setRules(entry.rules);
+      const challenge = await program.methods.currentChallenge().accountsPartial({ mineState: stateAddr }).view();
+      const words: string[] = challenge.requiredWords;
+      const text = entry.text.replace(/\{(\d)\}/g, (_, k: string) => words[Number(k)]!);
+
+      const miner = Keypair.generate();
+      const sig = await conn.requestAirdrop(miner.publicKey, LAMPORTS_PER_SOL);
+      await conn.confirmTransaction(sig, "confirmed");
+
+      const epoch: anchor.BN = challenge.epochNumber;
+      const [solution] = PublicKey.findProgramAddressSync(
+        [Buffer.from("solution"), miner.publicKey.toBuffer(), epoch.toArrayLike(Buffer, "le", 8)],
+        program.programId,
+      );
+      const nonce = grindNonce(
+        Buffer.from(challenge.challengeSeed),
+        miner.publicKey,
+        Buffer.from(text, "utf-8"),
+        challenge.difficulty.toNumber(),
+      );
+
+      const txSig = await program.methods
+        .submitSolution(text, new anchor.BN(nonce.toString()), miner.publicKey)
+        .accountsPartial({
+          mineState: stateAddr,
+          solution,
+          miner: miner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+        .signers([miner])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await conn.getTransaction(txSig, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+      const units = tx!.meta!.computeUnitsConsumed!;
+      measured[entry.name] = units;
+
+      const [beforeVerify, afterVerify, afterHash] = remainingUnits(tx!.meta!.logMessages ?? []);
+      const breakdown = afterHash !== undefined
+        ? ` (verify ${beforeVerify! - afterVerify!}, keccak ${afterVerify! - afterHash})`
+        : "";
+      console.log(`      ${text.length} bytes: ${units} CU${breakdown}`);
+
+      expect(units, "over the miner's compute limit").to.be.at.most(baseline.max_units);
+      const expected = baseline.submit_solution[entry.name];
+      if (!UPDATE) {
+        expect(expected, `no baseline for "${entry.name}"; record one with yarn cu-baseline`).to.be.a("number");
+        const limit = Math.floor(expected! * (1 + baseline.tolerance_pct / 100));
+        expect(units, `regressed from baseline ${expected}`).to.be.at.most(limit);
+      }
+    });
+  }
+});
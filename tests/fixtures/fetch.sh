@@ -0,0 +1,44 @@
+#!/bin/sh
+# Fetch the programs `anchor test` loads (`[[test.genesis]]` in Anchor.toml)
+# and check them against the hashes pinned in SHA256SUMS.
+#
+#   sh tests/fixtures/fetch.sh        dump missing fixtures from mainnet, then verify
+#   sh tests/fixtures/fetch.sh --pin  record the hash of the current files
+set -eu
+cd "$(dirname "$0")"
+
+NAME=mpl_token_metadata.so
+ADDRESS=metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s
+
+sha256() {
+  if command -v sha256sum >/dev/null 2>&1; then sha256sum "$1"; else shasum -a 256 "$1"; fi | cut -d' ' -f1
+}
+
+if [ "${1:-}" = "--pin" ]; then
+  [ -f "$NAME" ] || { echo "tests/fixtures/$NAME is missing; fetch it first" >&2; exit 1; }
+  echo "$(sha256 "$NAME")  $NAME" > SHA256SUMS
+  echo "Pinned $NAME"
+  exit 0
+fi
+
+if [ ! -f "$NAME" ]; then
+  if ! command -v solana >/dev/null 2>&1; then
+    echo "tests/fixtures/$NAME is missing and the Solana CLI is not installed to dump it." >&2
+    echo "Install it, or copy the program there by hand (see tests/fixtures/README.md)." >&2
+    exit 1
+  fi
+  echo "Dumping $NAME from mainnet..."
+  solana program dump -um "$ADDRESS" "$NAME"
+fi
+
+pinned=$(grep " $NAME\$" SHA256SUMS 2>/dev/null | cut -d' ' -f1)
+actual=$(sha256 "$NAME")
+if [ -z "$pinned" ]; then
+  echo "warning: no hash pinned for $NAME; review it, then record it with: sh tests/fixtures/fetch.sh --pin" >&2
+elif [ "$pinned" != "$actual" ]; then
+  echo "tests/fixtures/$NAME does not match the pinned hash (was the mainnet program upgraded?)" >&2
+  echo "  pinned: $pinned" >&2
+  echo "  actual: $actual" >&2
+  echo "Delete it and fetch again, or review the new version and re-pin with --pin." >&2
+  exit 1
+fi
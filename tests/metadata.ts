@@ -0,0 +1,131 @@
+/**
+ * Token metadata lifecycle: create → update → finalize.
+ * Requires the Metaplex fixture (tests/fixtures/README.md).
+ */
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { Metadata, PROGRAM_ID as TOKEN_METADATA_PROGRAM_ID } from "@metaplex-foundation/mpl-token-metadata";
+import { expect } from "chai";
+import { Poi } from "../target/types/poi";
+
+describe("metadata", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.Poi as Program<Poi>;
+
+  const [stateAddr] = PublicKey.findProgramAddressSync([Buffer.from("mine_state")], program.programId);
+  const [mintAddr] = PublicKey.findProgramAddressSync([Buffer.from("mint")], program.programId);
+  const [metadataAddr] = PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mintAddr.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID,
+  );
+
+  // Metaplex pads name/symbol/uri with NULs
+  const trim = (s: string) => s.replace(/\0/g, "");
+
+  async function fetchMetadata() {
+    return Metadata.fromAccountAddress(provider.connection, metadataAddr);
+  }
+
+  before(async () => {
+    // Another suite may have initialized the shared validator already
+    if (await provider.connection.getAccountInfo(stateAddr)) return;
+    await program.methods
+      .initialize()
+      .accountsPartial({
+        mineState: stateAddr,
+        mint: mintAddr,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+  });
+
+  it("creates mutable metadata", async () => {
+    await program.methods
+      .createMetadata("Crab", "CRB", "https://example.com/crb.json")
+      .accountsPartial({
+        mineState: stateAddr,
+        mint: mintAddr,
+        metadata: metadataAddr,
+        payer: provider.wallet.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc();
+
+    const md = await fetchMetadata();
+    expect(trim(md.data.name)).to.equal("Crab");
+    expect(md.updateAuthority.toBase58()).to.equal(stateAddr.toBase58());
+    expect(md.isMutable).to.equal(true);
+  });
+
+  it("updates name, symbol and uri", async () => {
+    await program.methods
+      .updateMetadata("CRB Token", "CRB", "https://example.com/crb-v2.json")
+      .accountsPartial({
+        mineState: stateAddr,
+        metadata: metadataAddr,
+        authority: provider.wallet.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc();
+
+    const md = await fetchMetadata();
+    expect(trim(md.data.name)).to.equal("CRB Token");
+    expect(trim(md.data.uri)).to.equal("https://example.com/crb-v2.json");
+  });
+
+  it("rejects updates from a non-authority", async () => {
+    const stranger = Keypair.generate();
+    try {
+      await program.methods
+        .updateMetadata("Rug", "RUG", "https://example.com/rug.json")
+        .accountsPartial({
+          mineState: stateAddr,
+          metadata: metadataAddr,
+          authority: stranger.publicKey,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([stranger])
+        .rpc();
+      expect.fail("update by non-authority should fail");
+    } catch (e: any) {
+      expect(e.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("finalizes metadata and blocks further updates", async () => {
+    await program.methods
+      .finalizeMetadata()
+      .accountsPartial({
+        mineState: stateAddr,
+        metadata: metadataAddr,
+        authority: provider.wallet.publicKey,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .rpc();
+
+    const md = await fetchMetadata();
+    expect(md.isMutable).to.equal(false);
+
+    let failed = false;
+    try {
+      await program.methods
+        .updateMetadata("Changed", "CRB", "https://example.com/changed.json")
+        .accountsPartial({
+          mineState: stateAddr,
+          metadata: metadataAddr,
+          authority: provider.wallet.publicKey,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .rpc();
+    } catch {
+      failed = true;
+    }
+    expect(failed, "update after finalize should fail").to.equal(true);
+    expect(trim((await fetchMetadata()).data.name)).to.equal("CRB Token");
+  });
+});